@@ -0,0 +1,50 @@
+//! Time serializing a prove request for the fixture circuit with serde_json
+//! and with simd-json, the two encoders behind the `simd-json` feature
+//!
+//! Run with `cargo bench --features simd-json --bench serialize`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 200;
+
+fn main() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../fixture/substring_sha256.json"
+    );
+    let circuit: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let body = serde_json::json!({
+        "circuit": circuit,
+        "input": { "text": "hello world", "start": "0", "len": "5" },
+        "backend": "ultra_honk",
+    });
+    let len = serde_json::to_vec(&body).unwrap().len();
+    println!(
+        "prove request body: {} bytes, best and median of {} runs",
+        len, RUNS
+    );
+
+    report("serde_json", || {
+        serde_json::to_vec(black_box(&body)).unwrap()
+    });
+    report("simd-json", || simd_json::to_vec(black_box(&body)).unwrap());
+}
+
+fn report(name: &str, mut serialize: impl FnMut() -> Vec<u8>) {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let started = Instant::now();
+            black_box(serialize());
+            started.elapsed()
+        })
+        .collect();
+    times.sort();
+    println!(
+        "{:>10}: best {:?}, median {:?}",
+        name,
+        times[0],
+        times[RUNS / 2]
+    );
+}
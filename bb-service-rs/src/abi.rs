@@ -0,0 +1,177 @@
+use crate::{BbServiceError, CompiledCircuit, ProofData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Name given to the circuit's return value in [`named_public_inputs`]
+const RETURN_NAME: &str = "return";
+
+/// Typed view of a compiled Noir circuit artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompiledArtifact {
+    pub bytecode: String,
+    pub abi: CircuitAbi,
+}
+
+/// ABI of a circuit's entry point
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitAbi {
+    pub parameters: Vec<AbiParameter>,
+    pub return_type: Option<AbiReturnType>,
+}
+
+/// A named circuit parameter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: AbiType,
+    pub visibility: AbiVisibility,
+}
+
+/// Type and visibility of a circuit's return value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiReturnType {
+    pub abi_type: AbiType,
+    pub visibility: AbiVisibility,
+}
+
+/// Whether a value is part of the proof's public inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AbiVisibility {
+    Public,
+    Private,
+    Databus,
+}
+
+/// Signedness of an integer type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sign {
+    Unsigned,
+    Signed,
+}
+
+/// A Noir ABI type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AbiType {
+    Field,
+    Boolean,
+    Integer {
+        sign: Sign,
+        width: u32,
+    },
+    Array {
+        length: usize,
+        #[serde(rename = "type")]
+        elem: Box<AbiType>,
+    },
+    String {
+        length: usize,
+    },
+    Struct {
+        path: String,
+        fields: Vec<AbiField>,
+    },
+    Tuple {
+        fields: Vec<AbiType>,
+    },
+}
+
+/// A named field of a struct type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: AbiType,
+}
+
+/// Parse the typed view of a circuit's bytecode and ABI
+///
+/// Other artifact fields (debug symbols, file map, ...) are ignored, the
+/// [`CompiledCircuit`] itself is still what gets sent to the service. A
+/// circuit whose ABI doesn't parse is reported as [`BbServiceError::Circuit`].
+pub fn parse_artifact(value: &CompiledCircuit) -> Result<CompiledArtifact, BbServiceError> {
+    CompiledArtifact::deserialize(value)
+        .map_err(|e| BbServiceError::Circuit(format!("Failed to parse circuit ABI: {}", e)))
+}
+
+/// The type of a circuit's return value, `None` for circuits that return
+/// nothing
+///
+/// Only the ABI's `return_type` is read, so this works on artifacts without
+/// bytecode too. Use it to decode the trailing public inputs of a proof when
+/// the return value is public.
+pub fn circuit_return_type(circuit: &CompiledCircuit) -> Result<Option<AbiType>, BbServiceError> {
+    let abi = circuit
+        .get("abi")
+        .ok_or_else(|| BbServiceError::Circuit("Circuit JSON must contain an 'abi'".to_string()))?;
+    let return_type = abi.get("return_type").unwrap_or(&serde_json::Value::Null);
+    Option::<AbiReturnType>::deserialize(return_type)
+        .map(|return_type| return_type.map(|return_type| return_type.abi_type))
+        .map_err(|e| BbServiceError::Circuit(format!("Failed to parse circuit return type: {}", e)))
+}
+
+/// Map each public input of `proof` to its name in the circuit ABI, with the
+/// field element as a `0x`-prefixed hex string
+///
+/// Public parameters come first in ABI order, followed by a public return
+/// value named `return`. Composite values are flattened one field element at a
+/// time: array and string elements as `name[0]`, struct fields as `name.field`
+/// and tuple elements as `name.0`. Fails if the proof doesn't carry exactly as
+/// many public inputs as the ABI declares.
+pub fn named_public_inputs(
+    circuit: &CompiledCircuit,
+    proof: &ProofData,
+) -> Result<HashMap<String, String>, BbServiceError> {
+    let artifact = parse_artifact(circuit)?;
+
+    let mut names = Vec::new();
+    for parameter in &artifact.abi.parameters {
+        if parameter.visibility == AbiVisibility::Public {
+            flatten_names(parameter.name.clone(), &parameter.param_type, &mut names);
+        }
+    }
+    if let Some(return_type) = &artifact.abi.return_type
+        && return_type.visibility == AbiVisibility::Public
+    {
+        flatten_names(RETURN_NAME.to_string(), &return_type.abi_type, &mut names);
+    }
+
+    let fields = proof.public_input_fields()?;
+    if fields.len() != names.len() {
+        return Err(BbServiceError::InvalidProof(format!(
+            "proof has {} public inputs but the circuit ABI declares {}",
+            fields.len(),
+            names.len()
+        )));
+    }
+
+    Ok(names.into_iter().zip(fields).collect())
+}
+
+/// Push the name of each field element making up a value of type `abi_type`
+fn flatten_names(name: String, abi_type: &AbiType, names: &mut Vec<String>) {
+    match abi_type {
+        AbiType::Field | AbiType::Boolean | AbiType::Integer { .. } => names.push(name),
+        AbiType::Array { length, elem } => {
+            for index in 0..*length {
+                flatten_names(format!("{}[{}]", name, index), elem, names);
+            }
+        }
+        AbiType::String { length } => {
+            names.extend((0..*length).map(|index| format!("{}[{}]", name, index)));
+        }
+        AbiType::Struct { fields, .. } => {
+            for field in fields {
+                flatten_names(format!("{}.{}", name, field.name), &field.field_type, names);
+            }
+        }
+        AbiType::Tuple { fields } => {
+            for (index, field_type) in fields.iter().enumerate() {
+                flatten_names(format!("{}.{}", name, index), field_type, names);
+            }
+        }
+    }
+}
@@ -0,0 +1,472 @@
+use crate::body::JsonBody;
+use crate::{
+    BbServiceClient, BbServiceError, CompiledCircuit, ErrorResponse, InputMap, ProofData,
+    ProofOptions, idle, proof, service_error_from_body, slot, validate_inputs,
+};
+use futures::future::join_all;
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::{Method, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Content type of a newline-delimited JSON stream
+const NDJSON: &str = "application/x-ndjson";
+
+/// Number of single-proof requests in flight when falling back from `/prove/batch`
+const BATCH_FALLBACK_CONCURRENCY: usize = 4;
+
+/// Request structure for batch proof generation
+#[derive(Debug, Serialize)]
+struct ProveBatchRequest<'a> {
+    circuit: &'a CompiledCircuit,
+    inputs: Vec<InputMap>,
+    #[serde(flatten)]
+    options: ProofOptions,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    recursive: bool,
+}
+
+/// Response structure for batch proof generation
+#[derive(Debug, Deserialize)]
+struct ProveBatchResponse {
+    #[allow(dead_code)]
+    message: String,
+    proofs: Vec<ProofData>,
+}
+
+/// Request structure for batch proof verification
+#[derive(Debug, Serialize)]
+struct VerifyBatchRequest<'a> {
+    circuit: &'a CompiledCircuit,
+    proofs: Vec<ProofData>,
+    #[serde(flatten)]
+    options: ProofOptions,
+}
+
+/// Response structure for batch proof verification
+#[derive(Debug, Deserialize)]
+struct VerifyBatchResponse {
+    #[allow(dead_code)]
+    message: String,
+    results: Vec<bool>,
+}
+
+impl BbServiceClient {
+    /// Generate proofs for several input sets of the same circuit in one request
+    ///
+    /// Proofs are returned in input order. Servers without a `/prove/batch`
+    /// endpoint (404 or 405) are handled by proving each input separately, with a
+    /// few requests in flight at once, and a failure then reports the index of
+    /// the input as [`BbServiceError::Batch`]. With
+    /// [`strict_inputs`](crate::BbServiceClientBuilder::strict_inputs) set,
+    /// every input is checked before the request is sent, and the first invalid
+    /// one is reported the same way.
+    pub async fn generate_proofs_batch(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        inputs: Vec<InputMap>,
+    ) -> Result<Vec<ProofData>, BbServiceError> {
+        let circuit = circuit.borrow();
+        let request = self.prove_batch_request(circuit, inputs)?;
+
+        match self
+            .post_json::<ProveBatchResponse>("/prove/batch", &request)
+            .await
+        {
+            Ok(response) if response.proofs.len() == request.inputs.len() => Ok(response.proofs),
+            Ok(_) => Err(BbServiceError::InvalidResponse),
+            Err(BbServiceError::Service { status, .. }) if is_unsupported(status) => {
+                let ProveBatchRequest { inputs, .. } = request;
+                self.generate_proofs_concurrent(circuit, inputs, BATCH_FALLBACK_CONCURRENCY)
+                    .await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Generate proofs for several input sets of the same circuit, with at most
+    /// `max_concurrency` requests in flight (a limit of 0 is treated as 1)
+    ///
+    /// Proofs are returned in input order. The circuit is shared by all requests
+    /// rather than copied per input. If any proof fails, the error for the first
+    /// failing input is returned as [`BbServiceError::Batch`].
+    pub async fn generate_proofs_concurrent(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        inputs: Vec<InputMap>,
+        max_concurrency: usize,
+    ) -> Result<Vec<ProofData>, BbServiceError> {
+        let semaphore = Semaphore::new(max_concurrency.max(1));
+        let circuit = circuit.borrow();
+        let semaphore = &semaphore;
+
+        let results = join_all(
+            inputs
+                .into_iter()
+                .enumerate()
+                .map(|(index, input)| async move {
+                    let _permit = semaphore
+                        .acquire()
+                        .await
+                        .expect("semaphore is never closed");
+                    self.prove(circuit, input)
+                        .await
+                        .map_err(|source| BbServiceError::Batch {
+                            index,
+                            source: Box::new(source),
+                        })
+                }),
+        )
+        .await;
+
+        results.into_iter().collect()
+    }
+
+    /// Generate proofs for several input sets of the same circuit, yielding
+    /// each proof as soon as it arrives
+    ///
+    /// Proofs come in input order. When the service streams its
+    /// `/prove/batch` response as newline-delimited JSON, one proof object per
+    /// line, each is yielded as its line completes, so processing can start
+    /// before the whole batch is done. An `{"error": ...}` line, an
+    /// interrupted stream or a line over the client's
+    /// [`max_response_size`](crate::BbServiceClientBuilder::max_response_size)
+    /// is yielded as a [`BbServiceError::Batch`] error for the input it was
+    /// meant for, and ends the stream. A service answering with a plain JSON
+    /// batch yields its proofs once it has finished, and servers without the
+    /// endpoint (404 or 405) are handled like in
+    /// [`generate_proofs_batch`](Self::generate_proofs_batch), yielding a
+    /// result, proof or error, for every input.
+    ///
+    /// The stream holds its own clone of the client, so it can outlive
+    /// `self`, and keeps the circuit behind an `Arc`: passing one that is
+    /// already shared doesn't copy it. Nothing is sent until it is first
+    /// polled, and with
+    /// [`strict_inputs`](crate::BbServiceClientBuilder::strict_inputs) set an
+    /// invalid input is yielded as the stream's only error before anything is
+    /// sent.
+    pub fn generate_proofs_stream<C: Into<Arc<CompiledCircuit>>>(
+        &self,
+        circuit: C,
+        inputs: Vec<InputMap>,
+    ) -> impl Stream<Item = Result<ProofData, BbServiceError>> + use<C> {
+        let client = self.clone();
+        let circuit = circuit.into();
+        stream::once(async move { client.open_proofs_stream(circuit, inputs).await }).flatten()
+    }
+
+    /// Send the batch request behind
+    /// [`generate_proofs_stream`](Self::generate_proofs_stream) and pick how
+    /// to read its response
+    async fn open_proofs_stream(
+        self,
+        circuit: Arc<CompiledCircuit>,
+        inputs: Vec<InputMap>,
+    ) -> impl Stream<Item = Result<ProofData, BbServiceError>> {
+        let request = match self.prove_batch_request(&circuit, inputs) {
+            Ok(request) => request,
+            Err(err) => return stream::iter(vec![Err(err)]).left_stream(),
+        };
+        let accept = HeaderMap::from_iter([(
+            ACCEPT,
+            HeaderValue::from_static("application/x-ndjson, application/json"),
+        )]);
+
+        let response = match self
+            .request(Method::POST, "/prove/batch")
+            .headers(accept)
+            .json_body(&request)
+        {
+            Ok(builder) => self.send(builder).await,
+            Err(err) => Err(err),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(err) => return stream::iter(vec![Err(err)]).left_stream(),
+        };
+        let status = response.status();
+        if is_unsupported(status.as_u16()) {
+            let ProveBatchRequest { inputs, .. } = request;
+            return self
+                .prove_each(circuit, inputs)
+                .right_stream()
+                .right_stream();
+        }
+
+        let is_ndjson = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with(NDJSON));
+        if status.is_success() && is_ndjson {
+            let limits = self.limits;
+            let sent = idle::sent_at(&response);
+            let slot = slot::of(&response);
+            let chunks = response
+                .bytes_stream()
+                .map(move |chunk| chunk.map_err(|err| self.request_error(err, sent)));
+            let lines = ProofLines {
+                chunks: Box::pin(chunks),
+                status: status.as_u16(),
+                buffer: Vec::new(),
+                index: 0,
+                expected: request.inputs.len(),
+                max_line_len: limits.max_response_size,
+                max_error_len: limits.max_error_len,
+                done: false,
+                _slot: slot,
+            };
+            return stream::unfold(lines, |mut lines| async move {
+                let item = lines.next_proof().await?;
+                Some((item, lines))
+            })
+            .left_stream()
+            .right_stream();
+        }
+
+        let proofs = self.whole_batch(response, request.inputs.len()).await;
+        stream::iter(match proofs {
+            Ok(proofs) => proofs.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        })
+        .left_stream()
+    }
+
+    /// Build a batch prove request with the client's proving options, checking
+    /// every input first if `strict_inputs` is set
+    ///
+    /// The first invalid input is reported as [`BbServiceError::Batch`] with
+    /// its index, before anything is sent.
+    fn prove_batch_request<'a>(
+        &self,
+        circuit: &'a CompiledCircuit,
+        inputs: Vec<InputMap>,
+    ) -> Result<ProveBatchRequest<'a>, BbServiceError> {
+        if self.strict_inputs {
+            for (index, input) in inputs.iter().enumerate() {
+                validate_inputs(circuit, input).map_err(|source| BbServiceError::Batch {
+                    index,
+                    source: Box::new(source),
+                })?;
+            }
+        }
+        Ok(ProveBatchRequest {
+            circuit,
+            inputs,
+            options: self.options,
+            recursive: self.recursive,
+        })
+    }
+
+    /// Parse a batch response that isn't streamed
+    async fn whole_batch(
+        &self,
+        response: Response,
+        expected: usize,
+    ) -> Result<Vec<ProofData>, BbServiceError> {
+        let response: ProveBatchResponse = self.json_response(response).await?;
+        if response.proofs.len() != expected {
+            return Err(BbServiceError::InvalidResponse);
+        }
+        Ok(response.proofs)
+    }
+
+    /// Prove each input separately, a few at a time, yielding results in
+    /// input order
+    fn prove_each(
+        self,
+        circuit: Arc<CompiledCircuit>,
+        inputs: Vec<InputMap>,
+    ) -> impl Stream<Item = Result<ProofData, BbServiceError>> {
+        stream::iter(inputs.into_iter().enumerate())
+            .map(move |(index, input)| {
+                let client = self.clone();
+                let circuit = circuit.clone();
+                async move {
+                    client
+                        .prove(&circuit, input)
+                        .await
+                        .map_err(|source| BbServiceError::Batch {
+                            index,
+                            source: Box::new(source),
+                        })
+                }
+            })
+            .buffered(BATCH_FALLBACK_CONCURRENCY)
+    }
+
+    /// Verify several proofs of the same circuit in one request
+    ///
+    /// Results are returned in proof order, `false` meaning the proof is
+    /// invalid. An error means a proof couldn't be checked at all and reports
+    /// its index as [`BbServiceError::Batch`]. Servers without a
+    /// `/verify/batch` endpoint (404 or 405) are handled by fetching the
+    /// verification key once, through the same cache as
+    /// [`verify_proof_cached`](Self::verify_proof_cached), and verifying each
+    /// proof against it with a few requests in flight at once.
+    pub async fn verify_proofs_batch(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        proofs: Vec<ProofData>,
+    ) -> Result<Vec<bool>, BbServiceError> {
+        let circuit = circuit.borrow();
+        for (index, proof) in proofs.iter().enumerate() {
+            proof::check_verifiable(proof).map_err(|source| BbServiceError::Batch {
+                index,
+                source: Box::new(source),
+            })?;
+        }
+        let request = VerifyBatchRequest {
+            circuit,
+            proofs,
+            options: self.options,
+        };
+
+        match self
+            .post_json::<VerifyBatchResponse>("/verify/batch", &request)
+            .await
+        {
+            Ok(response) if response.results.len() == request.proofs.len() => Ok(response.results),
+            Ok(_) => Err(BbServiceError::InvalidResponse),
+            Err(BbServiceError::Service { status, .. }) if is_unsupported(status) => {
+                let VerifyBatchRequest { proofs, .. } = request;
+                self.verify_proofs_with_vk(circuit, proofs).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Verify each proof against the circuit's verification key, with a few
+    /// requests in flight at once
+    async fn verify_proofs_with_vk(
+        &self,
+        circuit: &CompiledCircuit,
+        proofs: Vec<ProofData>,
+    ) -> Result<Vec<bool>, BbServiceError> {
+        let vk = self.cached_verification_key(circuit).await?;
+        let semaphore = Semaphore::new(BATCH_FALLBACK_CONCURRENCY);
+        let vk = &vk;
+        let semaphore = &semaphore;
+
+        let results = join_all(
+            proofs
+                .into_iter()
+                .enumerate()
+                .map(|(index, proof)| async move {
+                    let _permit = semaphore
+                        .acquire()
+                        .await
+                        .expect("semaphore is never closed");
+                    self.verify_proof_with_vk(vk.clone(), proof)
+                        .await
+                        .map_err(|source| BbServiceError::Batch {
+                            index,
+                            source: Box::new(source),
+                        })
+                }),
+        )
+        .await;
+
+        results.into_iter().collect()
+    }
+}
+
+/// Reader for a newline-delimited stream of proofs, one per input
+struct ProofLines<S> {
+    chunks: Pin<Box<S>>,
+    status: u16,
+    buffer: Vec<u8>,
+    /// Index of the input the next line is for
+    index: usize,
+    expected: usize,
+    max_line_len: Option<usize>,
+    max_error_len: usize,
+    done: bool,
+    /// Kept until the stream is dropped
+    _slot: Option<slot::Slot>,
+}
+
+impl<S, B> ProofLines<S>
+where
+    S: Stream<Item = Result<B, BbServiceError>>,
+    B: AsRef<[u8]>,
+{
+    /// The next proof, `None` once every input has one or after an error
+    async fn next_proof(&mut self) -> Option<Result<ProofData, BbServiceError>> {
+        if self.done || self.index == self.expected {
+            return None;
+        }
+
+        let result = match self.next_line().await {
+            Ok(line) => parse_proof_line(&line, self.max_error_len),
+            Err(err) => Err(err),
+        };
+        let index = self.index;
+        self.index += 1;
+        self.done = result.is_err();
+        Some(result.map_err(|source| BbServiceError::Batch {
+            index,
+            source: Box::new(source),
+        }))
+    }
+
+    /// The next non-blank line of the stream
+    ///
+    /// A last line without a trailing newline still counts, a stream that
+    /// ends before it gives [`BbServiceError::InvalidResponse`].
+    async fn next_line(&mut self) -> Result<String, BbServiceError> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Ok(line.trim().to_string());
+            }
+            if let Some(limit) = self.max_line_len
+                && self.buffer.len() > limit
+            {
+                return Err(BbServiceError::ResponseTooLarge {
+                    status: self.status,
+                    limit,
+                });
+            }
+
+            match self.chunks.next().await {
+                Some(chunk) => self.buffer.extend_from_slice(chunk?.as_ref()),
+                None if self.buffer.iter().all(u8::is_ascii_whitespace) => {
+                    return Err(BbServiceError::InvalidResponse);
+                }
+                None => {
+                    let line = String::from_utf8_lossy(&self.buffer).trim().to_string();
+                    self.buffer.clear();
+                    return Ok(line);
+                }
+            }
+        }
+    }
+}
+
+/// Parse one line of a proof stream, either a proof or the service's error
+///
+/// Errors are reported as a 500 [`BbServiceError::Service`] since the
+/// response status has already been sent by then.
+fn parse_proof_line(line: &str, max_error_len: usize) -> Result<ProofData, BbServiceError> {
+    match serde_json::from_str(line) {
+        Ok(proof) => Ok(proof),
+        Err(_) if serde_json::from_str::<ErrorResponse>(line).is_ok() => Err(
+            service_error_from_body(StatusCode::INTERNAL_SERVER_ERROR, line, max_error_len),
+        ),
+        Err(_) => Err(BbServiceError::InvalidResponse),
+    }
+}
+
+/// Whether a status means the server doesn't have the endpoint
+pub(crate) fn is_unsupported(status: u16) -> bool {
+    status == StatusCode::NOT_FOUND.as_u16() || status == StatusCode::METHOD_NOT_ALLOWED.as_u16()
+}
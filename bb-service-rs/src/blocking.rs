@@ -0,0 +1,287 @@
+//! Synchronous client for scripts and tooling that don't run an async runtime
+//!
+//! Enabled with the `blocking` cargo feature. [`BlockingBbServiceClient`] must
+//! not be used from inside an async runtime, where reqwest's blocking client
+//! panics.
+
+use crate::body::JsonBody;
+use crate::observer::SharedObserver;
+use crate::retry::RetryPolicy;
+use crate::{
+    BbServiceClientBuilder, BbServiceError, BodyLimits, CompiledCircuit, InputMap, ProofData,
+    ProofOptions, ProveRequest, ProveResponse, VerifyRequest, VerifyResponse, parse_success_body,
+    proof, rate_limited, retry, service_error_from_body, validate_inputs,
+};
+use reqwest::Method;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{ACCEPT, HeaderMap, HeaderName, HeaderValue};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::borrow::Borrow;
+use std::io::Read;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Blocking counterpart of [`BbServiceClient`](crate::BbServiceClient)
+pub struct BlockingBbServiceClient {
+    pub(crate) client: Client,
+    pub(crate) base_url: String,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) auth: Option<(HeaderName, HeaderValue)>,
+    pub(crate) headers: HeaderMap,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) strict_inputs: bool,
+    pub(crate) options: ProofOptions,
+    pub(crate) recursive: bool,
+    pub(crate) idempotency_keys: bool,
+    pub(crate) limits: BodyLimits,
+    pub(crate) observer: Option<SharedObserver>,
+}
+
+impl std::fmt::Debug for BlockingBbServiceClient {
+    /// Shows the configuration with credentials and sensitive headers masked
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingBbServiceClient")
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("auth", &crate::redact::Auth(&self.auth))
+            .field("headers", &crate::redact::Headers(&self.headers))
+            .field("retry", &self.retry)
+            .field("strict_inputs", &self.strict_inputs)
+            .field("options", &self.options)
+            .field("recursive", &self.recursive)
+            .field("idempotency_keys", &self.idempotency_keys)
+            .field("limits", &self.limits)
+            .field("observer", &self.observer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BlockingBbServiceClient {
+    /// Create a new blocking bb-service client
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base_url` is not a valid http(s) URL or the client cannot be
+    /// built, use [`BlockingBbServiceClient::try_new`] or
+    /// [`BbServiceClientBuilder::build_blocking`] to handle the error instead.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::try_new(base_url).expect("failed to build bb-service client")
+    }
+
+    /// Create a new blocking bb-service client, failing if `base_url` is not a
+    /// valid http(s) URL
+    pub fn try_new(base_url: impl Into<String>) -> Result<Self, BbServiceError> {
+        BbServiceClientBuilder::default()
+            .base_url(base_url)
+            .build_blocking()
+    }
+
+    /// Create a new blocking bb-service client with default localhost URL
+    pub fn new_localhost() -> Self {
+        Self::new_localhost_port(3000)
+    }
+
+    /// Create a new blocking bb-service client for `http://localhost:{port}`
+    pub fn new_localhost_port(port: u16) -> Self {
+        BbServiceClientBuilder::default()
+            .base_url(format!("http://localhost:{}", port))
+            .build_blocking()
+            .expect("failed to build bb-service client")
+    }
+
+    /// Generate a proof using the bb-service
+    pub fn generate_proof(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+    ) -> Result<ProofData, BbServiceError> {
+        if self.strict_inputs {
+            validate_inputs(circuit.borrow(), &input)?;
+        }
+        let request = ProveRequest {
+            circuit: circuit.borrow(),
+            input,
+            options: self.options,
+            recursive: self.recursive,
+        };
+
+        let prove_response: ProveResponse =
+            self.send_json(self.prove_post().json_body(&request)?)?;
+        Ok(prove_response.proof)
+    }
+
+    /// Verify a proof using the bb-service, rejecting empty proof bytes without
+    /// a round trip
+    pub fn verify_proof(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        proof: ProofData,
+    ) -> Result<bool, BbServiceError> {
+        proof::check_verifiable(&proof)?;
+        let request = VerifyRequest {
+            circuit: circuit.borrow(),
+            proof,
+            options: self.options,
+        };
+
+        let verify_response: VerifyResponse = self.post_json("/verify", &request)?;
+        Ok(verify_response.is_valid)
+    }
+
+    /// Check if the bb-service is healthy/reachable
+    pub fn health_check(&self) -> Result<bool, BbServiceError> {
+        let response = self.send(self.request(Method::GET, "/health"))?;
+
+        Ok(response.status().is_success())
+    }
+
+    /// POST `body` as JSON to `path` and parse the JSON response on success
+    fn post_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, BbServiceError> {
+        self.send_json(self.request(Method::POST, path).json_body(body)?)
+    }
+
+    /// Send `request` and parse the JSON response on success
+    fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, BbServiceError> {
+        let response = self.send(request)?;
+
+        let status = response.status();
+        let request_id = crate::request_id::from_headers(response.headers());
+        let content_type = crate::content_type(response.headers());
+        let body = self.read_body(response)?;
+        if status.is_success() {
+            parse_success_body(status, content_type.as_deref(), &body)
+        } else {
+            Err(service_error_from_body(
+                status,
+                &String::from_utf8_lossy(&body),
+                self.limits.max_error_len,
+            )
+            .with_request_id(request_id))
+        }
+    }
+
+    /// Read a whole response body, failing once it exceeds the client's
+    /// response size limit
+    fn read_body(&self, response: Response) -> Result<Vec<u8>, BbServiceError> {
+        let status = response.status();
+        let Some(limit) = self.limits.max_response_size else {
+            return Ok(response.bytes()?.to_vec());
+        };
+
+        let mut body = Vec::new();
+        response.take(limit as u64 + 1).read_to_end(&mut body)?;
+        if body.len() > limit {
+            return Err(BbServiceError::ResponseTooLarge {
+                status: status.as_u16(),
+                limit,
+            });
+        }
+        Ok(body)
+    }
+
+    /// Send a request, retrying and reporting to the observer like the async
+    /// client
+    fn send(&self, request: RequestBuilder) -> Result<Response, BbServiceError> {
+        let Some(SharedObserver(observer)) = &self.observer else {
+            return self.send_with_retries(request);
+        };
+
+        let started = Instant::now();
+        observer.on_start();
+        let result = self.send_with_retries(request);
+        match &result {
+            Ok(response) => observer.on_success(response.status(), started.elapsed()),
+            Err(err) => observer.on_error(err),
+        }
+        result
+    }
+
+    /// The retry loop of [`send`](Self::send)
+    fn send_with_retries(&self, request: RequestBuilder) -> Result<Response, BbServiceError> {
+        let started = Instant::now();
+        let mut attempt = 0;
+        loop {
+            // Bodies are always buffered JSON, so cloning only fails for streams
+            let Some(current) = request.try_clone() else {
+                let result = request.send();
+                #[cfg(feature = "log")]
+                crate::logging::finished(&result);
+                return rate_limit_error(result?);
+            };
+            let result = current.send();
+
+            let retryable = match &result {
+                Ok(response) => RetryPolicy::should_retry_status(response.status()),
+                Err(err) => RetryPolicy::should_retry_error(err),
+            };
+            let retry_after = result
+                .as_ref()
+                .ok()
+                .and_then(|response| retry::retry_after(response.headers()));
+            let delay = retry_after.unwrap_or_else(|| self.retry.backoff(attempt));
+            if attempt >= self.retry.max_retries
+                || !retryable
+                || !self.retry.accepts_retry_after(retry_after)
+                || !self.retry.within_deadline(started.elapsed(), delay)
+            {
+                #[cfg(feature = "log")]
+                crate::logging::finished(&result);
+                return rate_limit_error(result?);
+            }
+            #[cfg(feature = "log")]
+            crate::logging::retrying(&result, delay, attempt);
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Start a `POST /prove` request, carrying a fresh idempotency key if
+    /// enabled
+    fn prove_post(&self) -> RequestBuilder {
+        let request = self.request(Method::POST, "/prove");
+        if self.idempotency_keys {
+            request.header(crate::IDEMPOTENCY_KEY_HEADER, crate::idempotency::new_key())
+        } else {
+            request
+        }
+    }
+
+    /// Start a request to `path` on the service with the client's settings applied
+    ///
+    /// Asks for JSON unless the client's headers set their own `Accept`.
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = format!("{}{}", self.base_url, path);
+        #[cfg(feature = "log")]
+        crate::logging::sending(&method, &url);
+        let request = self
+            .client
+            .request(method, url)
+            .headers(self.headers.clone());
+        let request = match &self.auth {
+            Some((name, value)) => request.header(name, value),
+            None => request,
+        };
+        let request = if self.headers.contains_key(ACCEPT) {
+            request
+        } else {
+            request.header(ACCEPT, HeaderValue::from_static(crate::JSON))
+        };
+        match self.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        }
+    }
+}
+
+/// Turn a 429 response into [`BbServiceError::RateLimited`]
+fn rate_limit_error(response: Response) -> Result<Response, BbServiceError> {
+    match rate_limited(response.status(), response.headers()) {
+        Some(err) => Err(err),
+        None => Ok(response),
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{BbServiceClient, BbServiceError, CompiledCircuit, ProofData, circuit_hash};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Verification keys keyed by [`circuit_hash`]
+pub(crate) type VkCache = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// Verifier for one circuit that sends only its verification key with each
+/// proof, from [`BbServiceClient::prepare_verifier`]
+///
+/// Holds its own clone of the client, so it shares the connection pool and
+/// can outlive the client it was prepared from, but keeps the settings the
+/// client had at the time, including its base URL.
+#[derive(Clone)]
+pub struct PreparedVerifier {
+    client: BbServiceClient,
+    vk: Vec<u8>,
+}
+
+impl PreparedVerifier {
+    /// Verify a proof of the prepared circuit
+    pub async fn verify(&self, proof: ProofData) -> Result<bool, BbServiceError> {
+        self.client.verify_proof_with_vk(self.vk.clone(), proof).await
+    }
+
+    /// The circuit's verification key
+    pub fn vk(&self) -> &[u8] {
+        &self.vk
+    }
+}
+
+impl BbServiceClient {
+    /// Verify a proof, fetching the circuit's verification key once and reusing
+    /// it for later proofs of the same circuit
+    ///
+    /// Keys are cached in memory by [`circuit_hash`] and never expire, so a
+    /// client verifying many distinct circuits holds one key per circuit until
+    /// [`clear_vk_cache`](Self::clear_vk_cache) is called.
+    pub async fn verify_proof_cached(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        proof: ProofData,
+    ) -> Result<bool, BbServiceError> {
+        let vk = self.cached_verification_key(circuit).await?;
+        self.verify_proof_with_vk(vk, proof).await
+    }
+
+    /// The circuit's verification key, from the cache when already fetched
+    pub(crate) async fn cached_verification_key(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+    ) -> Result<Vec<u8>, BbServiceError> {
+        let hash = circuit_hash(circuit.borrow())?;
+
+        let cached = self.vk_cache.lock().unwrap().get(&hash).cloned();
+        match cached {
+            Some(vk) => Ok(vk),
+            None => {
+                let vk = self.get_verification_key(circuit).await?;
+                self.vk_cache.lock().unwrap().insert(hash, vk.clone());
+                Ok(vk)
+            }
+        }
+    }
+
+    /// Fetch a circuit's verification key once and return a verifier that
+    /// reuses it
+    ///
+    /// Saves serializing and uploading the whole circuit, and the service
+    /// deriving its key, for every proof. The key comes from the same cache as
+    /// [`verify_proof_cached`](Self::verify_proof_cached).
+    pub async fn prepare_verifier(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+    ) -> Result<PreparedVerifier, BbServiceError> {
+        Ok(PreparedVerifier {
+            client: self.clone(),
+            vk: self.cached_verification_key(circuit).await?,
+        })
+    }
+
+    /// Drop all cached verification keys
+    pub fn clear_vk_cache(&self) {
+        self.vk_cache.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{BbServiceClient, BbServiceError, ProvingBackend, batch};
+use serde::Deserialize;
+
+/// What the service's `/capabilities` endpoint reports it supports
+///
+/// Older services without the endpoint are reported with every field empty,
+/// meaning unknown rather than unsupported.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ServiceCapabilities {
+    /// Proving backends the service can prove and verify with, by their wire
+    /// name such as `ultra_honk`
+    pub backends: Vec<String>,
+    /// Largest circuit the service accepts, in gates
+    #[serde(rename = "maxCircuitSize")]
+    pub max_circuit_size: Option<u64>,
+    /// Optional features the service has enabled, such as `batch` or `jobs`
+    pub features: Vec<String>,
+}
+
+impl ServiceCapabilities {
+    /// Whether the service reported support for `backend`
+    pub fn supports_backend(&self, backend: ProvingBackend) -> bool {
+        let name = serde_json::to_value(backend).expect("backends serialize to strings");
+        self.backends
+            .iter()
+            .any(|supported| name == supported.as_str())
+    }
+
+    /// Whether the service reported the feature flag `feature`
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|supported| supported == feature)
+    }
+}
+
+impl BbServiceClient {
+    /// Ask the service which backends and features it supports
+    ///
+    /// Servers without a `/capabilities` endpoint (404 or 405) give
+    /// [`ServiceCapabilities::default`] instead of an error, so check for empty
+    /// fields before ruling a backend out.
+    pub async fn capabilities(&self) -> Result<ServiceCapabilities, BbServiceError> {
+        match self.get_json("/capabilities").await {
+            Err(BbServiceError::Service { status, .. }) if batch::is_unsupported(status) => {
+                Ok(ServiceCapabilities::default())
+            }
+            result => result,
+        }
+    }
+}
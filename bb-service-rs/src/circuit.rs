@@ -0,0 +1,362 @@
+use crate::{AbiParameter, AbiReturnType, BbServiceClient, BbServiceError, CompiledCircuit};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{InputMap, ProofData};
+use base64::prelude::{BASE64_STANDARD, Engine as _};
+use reqwest::Method;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+use std::io::{self, Read};
+
+/// Load and validate a compiled circuit artifact from `path`
+///
+/// A file that can't be read is reported as [`BbServiceError::Io`] and one
+/// that isn't a valid artifact as [`BbServiceError::Circuit`], the same errors
+/// the client returns, so a load-prove-verify pipeline needs only one error
+/// type.
+///
+/// Not available on wasm32, which has no filesystem. Fetch the artifact with
+/// [`BbServiceClient::load_circuit_from_url`] or embed it and use
+/// [`parse_circuit_definition`] instead.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn load_circuit_definition(path: &str) -> Result<CompiledCircuit, BbServiceError> {
+    load_circuit_definition_with(path, ValidationMode::Lenient).await
+}
+
+/// How thoroughly a circuit artifact is checked when it's loaded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Only check that the artifact is a JSON object with `bytecode` and
+    /// `abi` fields
+    #[default]
+    Lenient,
+    /// Also check that `bytecode` is non-empty base64 and that every ABI
+    /// parameter and the return type have the shape nargo emits
+    Strict,
+}
+
+/// Load a compiled circuit artifact from `path`, validating it according to
+/// `mode`
+///
+/// Errors are reported like in [`load_circuit_definition`]. In
+/// [`ValidationMode::Strict`] the [`BbServiceError::Circuit`] error names the
+/// first problem found, e.g. `abi.parameters[1] (y): unknown variant ...`. Not
+/// available on wasm32, use [`parse_circuit_definition_with`] there.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn load_circuit_definition_with(
+    path: &str,
+    mode: ValidationMode,
+) -> Result<CompiledCircuit, BbServiceError> {
+    let circuit_content = tokio::fs::read(path)
+        .await
+        .map_err(|e| read_error(&format!("circuit file {}", path), e))?;
+
+    parse_circuit_definition_with(&circuit_content, mode)
+}
+
+/// Load and validate a compiled circuit artifact from `path`, blocking the
+/// current thread
+///
+/// Prefer [`load_circuit_definition`] inside an async runtime. Not available
+/// on wasm32.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_circuit_definition_blocking(path: &str) -> Result<CompiledCircuit, BbServiceError> {
+    let circuit_content =
+        fs::read(path).map_err(|e| read_error(&format!("circuit file {}", path), e))?;
+
+    parse_circuit_definition(&circuit_content)
+}
+
+/// What [`load_circuits_from_dir`] does with a file that isn't a valid circuit
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidCircuitPolicy {
+    /// Leave the file out of the result
+    Skip,
+    /// Fail the whole load with the file's [`BbServiceError::Circuit`] error
+    Fail,
+}
+
+/// Load every `*.json` circuit artifact in `dir`, keyed by file stem
+///
+/// With the `gzip` feature `*.json.gz` files are loaded too, keyed by the name
+/// before `.json.gz`. Each file is validated like in [`load_circuit_definition`], and files that
+/// fail validation are skipped or fail the load depending on `on_invalid`.
+/// Subdirectories are not searched, and errors reading the directory or a file
+/// are always returned. Not available on wasm32.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn load_circuits_from_dir(
+    dir: &str,
+    on_invalid: InvalidCircuitPolicy,
+) -> Result<HashMap<String, CompiledCircuit>, BbServiceError> {
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .map_err(|e| read_error(&format!("circuit directory {}", dir), e))?;
+
+    let mut circuits = HashMap::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let Some(stem) = circuit_file_stem(&path) else {
+            continue;
+        };
+        if !entry.file_type().await?.is_file() {
+            continue;
+        }
+
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| read_error(&format!("circuit file {}", path.display()), e))?;
+        match parse_circuit_definition(&bytes) {
+            Ok(circuit) => {
+                circuits.insert(stem, circuit);
+            }
+            Err(_) if on_invalid == InvalidCircuitPolicy::Skip => {}
+            // Prefix the file to the inner detail, not to the `Display`
+            // output, which would repeat "Invalid circuit: "
+            Err(BbServiceError::Circuit(detail)) => {
+                return Err(BbServiceError::Circuit(format!(
+                    "{}: {}",
+                    path.display(),
+                    detail
+                )));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(circuits)
+}
+
+/// The name a circuit file is keyed by in [`load_circuits_from_dir`], or `None`
+/// if it isn't a circuit artifact
+#[cfg(not(target_arch = "wasm32"))]
+fn circuit_file_stem(path: &std::path::Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy();
+    let stem = name.strip_suffix(".json");
+    #[cfg(feature = "gzip")]
+    let stem = stem.or_else(|| name.strip_suffix(".json.gz"));
+    stem.filter(|stem| !stem.is_empty()).map(str::to_string)
+}
+
+/// Load and validate a compiled circuit artifact from any reader, e.g. an
+/// object storage download or an embedded resource
+pub fn load_circuit_from_reader<R: Read>(mut reader: R) -> Result<CompiledCircuit, BbServiceError> {
+    let mut circuit_content = Vec::new();
+    reader
+        .read_to_end(&mut circuit_content)
+        .map_err(|e| read_error("circuit", e))?;
+
+    parse_circuit_definition(&circuit_content)
+}
+
+/// Magic bytes at the start of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Parse circuit JSON and check it has the fields the service needs
+///
+/// With the `gzip` feature, gzipped artifacts are recognised by their magic
+/// bytes and decompressed first, so every loader accepts them. Without it they
+/// fail with a [`BbServiceError::Circuit`] saying so.
+pub fn parse_circuit_definition(bytes: &[u8]) -> Result<CompiledCircuit, BbServiceError> {
+    let decompressed;
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        decompressed = gunzip(bytes)?;
+        decompressed.as_slice()
+    } else {
+        bytes
+    };
+
+    let circuit_json: serde_json::Value = serde_json::from_slice(bytes)
+        .map_err(|e| BbServiceError::Circuit(format!("Failed to parse circuit JSON: {}", e)))?;
+
+    // Validate that it contains the essential fields
+    let Some(obj) = circuit_json.as_object() else {
+        return Err(BbServiceError::Circuit(
+            "Circuit JSON must be an object".to_string(),
+        ));
+    };
+
+    if !obj.contains_key("bytecode") || !obj.contains_key("abi") {
+        return Err(BbServiceError::Circuit(
+            "Circuit JSON must contain 'bytecode' and 'abi' fields".to_string(),
+        ));
+    }
+
+    // Return the entire JSON object as-is
+    Ok(circuit_json)
+}
+
+/// Parse circuit JSON, validating it according to `mode`
+///
+/// [`ValidationMode::Lenient`] is the same as [`parse_circuit_definition`].
+pub fn parse_circuit_definition_with(
+    bytes: &[u8],
+    mode: ValidationMode,
+) -> Result<CompiledCircuit, BbServiceError> {
+    let circuit = parse_circuit_definition(bytes)?;
+    if mode == ValidationMode::Strict {
+        validate_strict(&circuit)?;
+    }
+    Ok(circuit)
+}
+
+/// Check the bytecode and ABI of a circuit that passed lenient validation,
+/// failing on the first structural problem
+fn validate_strict(circuit: &CompiledCircuit) -> Result<(), BbServiceError> {
+    let invalid = |at: &str, problem: String| {
+        BbServiceError::Circuit(format!("Invalid circuit artifact at {}: {}", at, problem))
+    };
+
+    if circuit_bytecode_bytes(circuit)?.is_empty() {
+        return Err(invalid("bytecode", "bytecode is empty".to_string()));
+    }
+
+    let abi = &circuit["abi"];
+    if !abi.is_object() {
+        return Err(invalid("abi", "expected an object".to_string()));
+    }
+    let Some(parameters) = abi["parameters"].as_array() else {
+        return Err(invalid(
+            "abi.parameters",
+            "expected an array of parameters".to_string(),
+        ));
+    };
+    for (index, parameter) in parameters.iter().enumerate() {
+        if let Err(e) = AbiParameter::deserialize(parameter) {
+            let at = match parameter["name"].as_str() {
+                Some(name) => format!("abi.parameters[{}] ({})", index, name),
+                None => format!("abi.parameters[{}]", index),
+            };
+            return Err(invalid(&at, e.to_string()));
+        }
+    }
+
+    let return_type = &abi["return_type"];
+    if !return_type.is_null()
+        && let Err(e) = AbiReturnType::deserialize(return_type)
+    {
+        return Err(invalid("abi.return_type", e.to_string()));
+    }
+    Ok(())
+}
+
+/// Compute a stable identity for a circuit as a hex SHA-256 digest
+///
+/// Only the `bytecode` field is hashed, as its UTF-8 text with surrounding
+/// whitespace trimmed. The ABI, debug symbols, file map and compiler metadata
+/// are excluded, so artifacts that only differ in those hash the same.
+pub fn circuit_hash(circuit: &CompiledCircuit) -> Result<String, BbServiceError> {
+    Ok(hex::encode(Sha256::digest(bytecode(circuit)?.as_bytes())))
+}
+
+/// Decode a circuit's base64 `bytecode` field into the raw ACIR bytes
+///
+/// Nargo gzips the bytecode before encoding it, so the result is usually a
+/// gzip stream, which is what size checks and hashes want. Fails with
+/// [`BbServiceError::Circuit`] if the field is missing or isn't valid base64.
+pub fn circuit_bytecode_bytes(circuit: &CompiledCircuit) -> Result<Vec<u8>, BbServiceError> {
+    BASE64_STANDARD.decode(bytecode(circuit)?).map_err(|e| {
+        BbServiceError::Circuit(format!("Circuit bytecode is not valid base64: {}", e))
+    })
+}
+
+/// The Noir compiler version recorded in a nargo artifact's `noir_version`
+/// field, e.g. `1.0.0-beta.9+6abff2f1...`
+///
+/// `None` for artifacts that don't record one, such as hand-built circuits.
+pub fn circuit_noir_version(circuit: &CompiledCircuit) -> Option<String> {
+    circuit["noir_version"].as_str().map(str::to_string)
+}
+
+/// Check that a circuit was compiled by a Noir version in `supported`,
+/// returning a warning to report if it wasn't
+///
+/// Artifacts from a compiler the service wasn't built against tend to fail
+/// with obscure proving errors, so run this before sending one. The build
+/// hash after `+` is ignored. Circuits with no or an unparseable
+/// `noir_version` also get a warning, since they can't be checked. Note that
+/// a requirement only matches pre-releases such as `1.0.0-beta.9` if it
+/// names a pre-release of the same version, e.g. `>=1.0.0-beta.3`.
+pub fn check_compatibility(circuit: &CompiledCircuit, supported: &VersionReq) -> Option<String> {
+    let Some(noir_version) = circuit_noir_version(circuit) else {
+        return Some("Circuit does not record the Noir version it was compiled with".to_string());
+    };
+    match Version::parse(&noir_version) {
+        Ok(version) if supported.matches(&version) => None,
+        Ok(_) => Some(format!(
+            "Circuit was compiled with Noir {}, the service supports {}",
+            noir_version, supported
+        )),
+        Err(_) => Some(format!(
+            "Circuit records an unrecognised Noir version: {}",
+            noir_version
+        )),
+    }
+}
+
+/// A circuit's `bytecode` string with surrounding whitespace trimmed
+fn bytecode(circuit: &CompiledCircuit) -> Result<&str, BbServiceError> {
+    circuit["bytecode"].as_str().map(str::trim).ok_or_else(|| {
+        BbServiceError::Circuit("Circuit JSON must contain a 'bytecode' string".to_string())
+    })
+}
+
+/// Decompress a gzipped circuit artifact
+#[cfg(feature = "gzip")]
+fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, BbServiceError> {
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(bytes)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| {
+            BbServiceError::Circuit(format!("Failed to decompress gzipped circuit: {}", e))
+        })?;
+    Ok(decompressed)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gunzip(_bytes: &[u8]) -> Result<Vec<u8>, BbServiceError> {
+    Err(BbServiceError::Circuit(
+        "Circuit is gzip-compressed, enable the `gzip` feature to load it".to_string(),
+    ))
+}
+
+/// Keep the kind of an I/O error while saying what was being read
+fn read_error(what: &str, err: io::Error) -> io::Error {
+    io::Error::new(err.kind(), format!("Failed to read {}: {}", what, err))
+}
+
+impl BbServiceClient {
+    /// Fetch a compiled circuit artifact from `url` and validate it like
+    /// [`load_circuit_definition`]
+    ///
+    /// The request goes through this client, so its timeout, retries, headers
+    /// and credentials apply.
+    pub async fn load_circuit_from_url(
+        &self,
+        url: &str,
+    ) -> Result<CompiledCircuit, BbServiceError> {
+        let response = self.send(self.request_url(Method::GET, url)).await?;
+        if !response.status().is_success() {
+            return Err(self.service_error(response).await);
+        }
+
+        let bytes = self.read_body(response).await?;
+        parse_circuit_definition(&bytes)
+    }
+
+    /// Load the circuit artifact at `circuit_path` and generate a proof for it
+    ///
+    /// Loading fails like [`load_circuit_definition`]. Not available on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn prove_from_file(
+        &self,
+        circuit_path: &str,
+        input: InputMap,
+    ) -> Result<ProofData, BbServiceError> {
+        let circuit = load_circuit_definition(circuit_path).await?;
+        self.generate_proof(circuit, input).await
+    }
+}
@@ -0,0 +1,109 @@
+use crate::platform::Instant;
+use crate::{BbServiceClient, BbServiceError};
+use reqwest::Method;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Details reported by the service's `/health` endpoint
+///
+/// Every field is optional since older services report only some of them, or
+/// return an empty body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct HealthStatus {
+    pub status: Option<String>,
+    pub version: Option<String>,
+    pub backend: Option<String>,
+    pub service: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+impl BbServiceClient {
+    /// Fetch the service's health details
+    ///
+    /// An empty body gives the default, detail-less status. Fails with
+    /// [`BbServiceError::Service`] if the service reports itself unhealthy
+    /// with a non-success status, and with
+    /// [`BbServiceError::MalformedResponse`] if a non-empty body isn't JSON,
+    /// like any other endpoint.
+    pub async fn health_status(&self) -> Result<HealthStatus, BbServiceError> {
+        let response = self.send(self.request(Method::GET, "/health")).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(self.service_error(response).await);
+        }
+
+        let content_type = crate::content_type(response.headers());
+        let body = self.read_body(response).await?;
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(HealthStatus::default());
+        }
+        crate::parse_success_body(status, content_type.as_deref(), &body)
+    }
+
+    /// Check that the service reports a version matching `expected`, as a
+    /// preflight check before deploying against it
+    ///
+    /// A leading `v` in the reported version is ignored. Fails with
+    /// [`BbServiceError::VersionUnknown`] if the service doesn't report a
+    /// version, and with [`BbServiceError::VersionMismatch`] if it reports one
+    /// that doesn't match or isn't a semver version.
+    pub async fn assert_version(&self, expected: &VersionReq) -> Result<(), BbServiceError> {
+        let actual = self
+            .health_status()
+            .await?
+            .version
+            .ok_or(BbServiceError::VersionUnknown)?;
+        match Version::parse(actual.trim().trim_start_matches('v')) {
+            Ok(version) if expected.matches(&version) => Ok(()),
+            _ => Err(BbServiceError::VersionMismatch {
+                expected: expected.to_string(),
+                actual,
+            }),
+        }
+    }
+
+    /// Check if the bb-service is healthy/reachable
+    ///
+    /// Returns `Ok(false)` when the service answers with an error status, and an
+    /// error when it can't be reached. A successful answer counts as healthy
+    /// even if its body isn't the service's health details, e.g. a proxy's
+    /// plain `OK`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                url = %format_args!("{}/health", self.base_url),
+                status = tracing::field::Empty,
+                elapsed_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn health_check(&self) -> Result<bool, BbServiceError> {
+        match self.health_status().await {
+            Ok(_) | Err(BbServiceError::MalformedResponse { .. }) => Ok(true),
+            Err(BbServiceError::Service { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Measure the round-trip time of a `/health` request
+    ///
+    /// Only the network exchange up to the response headers is timed, the body
+    /// is not read or parsed. Fails if the service is unreachable or answers with
+    /// an error status.
+    pub async fn ping(&self) -> Result<Duration, BbServiceError> {
+        let request = self.request(Method::GET, "/health");
+
+        let started = Instant::now();
+        let response = self.send(request).await?;
+        let elapsed = started.elapsed();
+
+        if !response.status().is_success() {
+            return Err(self.service_error(response).await);
+        }
+        Ok(elapsed)
+    }
+}
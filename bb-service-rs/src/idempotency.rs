@@ -0,0 +1,24 @@
+//! Idempotency keys for prove requests
+
+/// Header carrying the idempotency key of a prove request, see
+/// [`BbServiceClientBuilder::idempotency_keys`](crate::BbServiceClientBuilder::idempotency_keys)
+///
+/// The service, or a gateway in front of it, is expected to dedupe requests
+/// carrying the same key and answer a repeat with the first request's result
+/// instead of proving again.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// A random (version 4) UUID identifying one logical prove operation
+pub(crate) fn new_key() -> String {
+    let bits = fastrand::u128(..) & !(0xf000 << 64) & !(0xc000 << 48);
+    let bits = bits | (0x4000 << 64) | (0x8000 << 48);
+    let hex = format!("{:032x}", bits);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
@@ -0,0 +1,65 @@
+//! Idle timeouts, which reqwest reports like any other timeout
+//!
+//! A request that times out while its total timeout still has time left can
+//! only have been stopped by the idle timeout, so the client remembers when
+//! each request was sent and with which timeout, and compares against that.
+
+use crate::platform::Instant;
+use crate::{BbServiceClient, BbServiceError, platform};
+use reqwest::{Error as ReqwestError, Response};
+use std::time::Duration;
+
+/// When a request was sent and the total timeout it was given, kept in its
+/// response's extensions
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Sent {
+    pub(crate) at: Instant,
+    pub(crate) timeout: Option<Duration>,
+}
+
+/// Remember on `response` how its request was sent
+pub(crate) fn mark_sent(response: &mut Response, sent: Sent) {
+    #[cfg(not(target_arch = "wasm32"))]
+    response.extensions_mut().insert(sent);
+    #[cfg(target_arch = "wasm32")]
+    let _ = (response, sent);
+}
+
+/// How the request behind `response` was sent, if it went through
+/// [`mark_sent`]
+pub(crate) fn sent_at(response: &Response) -> Option<Sent> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return response.extensions().get::<Sent>().copied();
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = response;
+        None
+    }
+}
+
+impl BbServiceClient {
+    /// Convert an error from a request sent as `sent`, or from reading its
+    /// response, into a [`BbServiceError`]
+    ///
+    /// Timeouts count as idle while the total timeout hasn't passed yet, or
+    /// when there is none. Connect timeouts never do.
+    pub(crate) fn request_error(&self, err: ReqwestError, sent: Option<Sent>) -> BbServiceError {
+        let idle = self.idle_timeout.is_some()
+            && err.is_timeout()
+            && !platform::is_connect(&err)
+            && match sent {
+                Some(sent) => sent
+                    .timeout
+                    .is_none_or(|timeout| sent.at.elapsed() < timeout),
+                None => self.timeout.is_none(),
+            };
+        if idle {
+            BbServiceError::Timeout {
+                source: err,
+                idle: true,
+            }
+        } else {
+            err.into()
+        }
+    }
+}
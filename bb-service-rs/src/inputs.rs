@@ -0,0 +1,241 @@
+use crate::{AbiType, BbServiceError, CompiledCircuit, InputMap};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Modulus of the BN254 scalar field that circuit inputs live in, as
+/// lowercase hex without leading zeros
+const FIELD_MODULUS_HEX: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+/// Encode an integer as a field or integer input
+///
+/// Numbers are sent as decimal strings, e.g. `"42"`, never as JSON numbers:
+/// the service parses inputs in JavaScript, where numbers above 2^53 silently
+/// lose precision.
+pub fn to_field_value(x: impl Into<u128>) -> Value {
+    Value::String(x.into().to_string())
+}
+
+/// Encode a hex string as a field input
+///
+/// The canonical form is a `0x`-prefixed string of lowercase hex digits
+/// without leading zeros, e.g. `"0x1f"` (`"0x0"` for zero). The prefix is
+/// optional in `hex`. Fails with [`BbServiceError::InvalidInput`] if `hex`
+/// has no digits, contains a non-hex character or is not below the BN254
+/// field modulus, which the service would otherwise reject or reduce.
+pub fn hex_to_field_value(hex: &str) -> Result<Value, BbServiceError> {
+    let digits = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(BbServiceError::InvalidInput(format!(
+            "not a hex field value: {:?}",
+            hex
+        )));
+    }
+
+    let digits = digits.trim_start_matches('0').to_ascii_lowercase();
+    let digits = if digits.is_empty() { "0" } else { &digits };
+    // Without leading zeros, a longer number is larger and equal lengths
+    // compare like the numbers
+    if (digits.len(), digits) >= (FIELD_MODULUS_HEX.len(), FIELD_MODULUS_HEX) {
+        return Err(BbServiceError::InvalidInput(format!(
+            "hex value {} is not below the field modulus",
+            hex
+        )));
+    }
+    Ok(Value::String(format!("0x{}", digits)))
+}
+
+/// Builder for an [`InputMap`] in the shapes the bb-service expects
+///
+/// Field and integer values are sent as decimal strings so values above
+/// 2^53 survive the trip through JavaScript, see [`to_field_value`] and
+/// [`hex_to_field_value`] for the exact encoding.
+#[derive(Debug, Default, Clone)]
+pub struct InputBuilder {
+    input: InputMap,
+}
+
+impl InputBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a field or integer input, sent as a decimal string
+    pub fn field(self, name: impl Into<String>, value: u64) -> Self {
+        self.value(name, to_field_value(value))
+    }
+
+    /// Set a field or integer input from a value up to `u128`, sent as a
+    /// decimal string
+    pub fn field_u128(self, name: impl Into<String>, value: u128) -> Self {
+        self.value(name, to_field_value(value))
+    }
+
+    /// Set a field input from a hex string, adding the `0x` prefix if missing
+    ///
+    /// The digits are passed through unchanged and checked by the service,
+    /// use [`try_hex`](Self::try_hex) to check them up front.
+    pub fn hex(self, name: impl Into<String>, value: &str) -> Self {
+        let digits = value.strip_prefix("0x").unwrap_or(value);
+        self.value(name, format!("0x{}", digits))
+    }
+
+    /// Set a field input from a hex string in its canonical form, failing if
+    /// it isn't a valid field element, see [`hex_to_field_value`]
+    pub fn try_hex(self, name: impl Into<String>, value: &str) -> Result<Self, BbServiceError> {
+        Ok(self.value(name, hex_to_field_value(value)?))
+    }
+
+    /// Set a boolean input
+    pub fn bool(self, name: impl Into<String>, value: bool) -> Self {
+        self.value(name, value)
+    }
+
+    /// Set an array input, each element converted with `Into<Value>`
+    pub fn array<T: Into<Value>>(
+        self,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let values: Vec<Value> = values.into_iter().map(Into::into).collect();
+        self.value(name, values)
+    }
+
+    /// Set an input to any JSON value, e.g. a struct built with `json!`
+    pub fn value(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.input.insert(name.into(), value.into());
+        self
+    }
+
+    /// Finish building the input map
+    pub fn build(self) -> InputMap {
+        self.input
+    }
+}
+
+/// Check that `input` supplies exactly the parameters declared in the circuit ABI
+///
+/// Every name in `abi.parameters` must be present and no other keys may be
+/// given. The error lists all missing and unknown names at once. Each value
+/// must then have its parameter's shape: arrays, strings and tuples of the
+/// declared length, structs with all their fields and plain values where a
+/// field, integer or boolean is expected. The first mismatch is reported
+/// with its path, e.g. `foo.bar[2]`. Parameters whose type doesn't parse as
+/// an [`AbiType`] only get the presence check.
+pub fn validate_inputs(circuit: &CompiledCircuit, input: &InputMap) -> Result<(), BbServiceError> {
+    let parameters = circuit["abi"]["parameters"].as_array().ok_or_else(|| {
+        BbServiceError::Circuit("circuit ABI has no 'parameters' array".to_string())
+    })?;
+
+    let names = parameters
+        .iter()
+        .map(|parameter| {
+            parameter["name"].as_str().ok_or_else(|| {
+                BbServiceError::Circuit("circuit ABI parameter has no 'name'".to_string())
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let missing: Vec<&str> = names
+        .iter()
+        .copied()
+        .filter(|name| !input.contains_key(*name))
+        .collect();
+    let mut unknown: Vec<&str> = input
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !names.contains(key))
+        .collect();
+    unknown.sort_unstable();
+
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!("missing inputs: {}", missing.join(", ")));
+    }
+    if !unknown.is_empty() {
+        problems.push(format!("unknown inputs: {}", unknown.join(", ")));
+    }
+    if !problems.is_empty() {
+        return Err(BbServiceError::InvalidInput(problems.join("; ")));
+    }
+
+    for (name, parameter) in names.into_iter().zip(parameters) {
+        if let Ok(abi_type) = AbiType::deserialize(&parameter["type"]) {
+            check_shape(name.to_string(), &abi_type, &input[name])
+                .map_err(BbServiceError::InvalidInput)?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that `value`, found at `path`, has the shape of `abi_type`
+fn check_shape(path: String, abi_type: &AbiType, value: &Value) -> Result<(), String> {
+    let mismatch = |expected: String| {
+        Err(format!(
+            "{}: expected {}, got {}",
+            path,
+            expected,
+            kind(value)
+        ))
+    };
+    match abi_type {
+        AbiType::Field | AbiType::Integer { .. } => match value {
+            Value::String(_) | Value::Number(_) => Ok(()),
+            _ => mismatch("a number or numeric string".to_string()),
+        },
+        AbiType::Boolean => match value {
+            Value::Bool(_) => Ok(()),
+            _ => mismatch("a boolean".to_string()),
+        },
+        AbiType::String { length } => match value {
+            Value::String(string) if string.len() == *length => Ok(()),
+            _ => mismatch(format!("a string of {} bytes", length)),
+        },
+        AbiType::Array { length, elem } => match value {
+            Value::Array(elements) if elements.len() == *length => {
+                for (index, element) in elements.iter().enumerate() {
+                    check_shape(format!("{}[{}]", path, index), elem, element)?;
+                }
+                Ok(())
+            }
+            _ => mismatch(format!("an array of {} elements", length)),
+        },
+        AbiType::Tuple { fields } => match value {
+            Value::Array(elements) if elements.len() == fields.len() => {
+                for (index, (field_type, element)) in fields.iter().zip(elements).enumerate() {
+                    check_shape(format!("{}.{}", path, index), field_type, element)?;
+                }
+                Ok(())
+            }
+            _ => mismatch(format!("a tuple of {} elements", fields.len())),
+        },
+        AbiType::Struct { fields, .. } => {
+            let Value::Object(object) = value else {
+                return mismatch("a struct".to_string());
+            };
+            for field in fields {
+                let field_path = format!("{}.{}", path, field.name);
+                match object.get(&field.name) {
+                    Some(field_value) => check_shape(field_path, &field.field_type, field_value)?,
+                    None => return Err(format!("{}: missing field", field_path)),
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Describe a JSON value for a shape mismatch
+fn kind(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) => "a boolean".to_string(),
+        Value::Number(_) => "a number".to_string(),
+        Value::String(string) => format!("a string of {} bytes", string.len()),
+        Value::Array(elements) => format!("an array of {} elements", elements.len()),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    BbServiceClient, BbServiceError, CancellationToken, CompiledCircuit, InputMap, ProofData,
+    platform,
+};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Identifier of a proof job submitted to the bb-service
+///
+/// Job ids are embedded in request paths, so they must be non-empty, contain
+/// no whitespace, `/`, `\`, `?`, `#` or `%`, and not be `.` or `..`, which
+/// would resolve to another endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct JobId(String);
+
+impl JobId {
+    /// The job id as sent to the service
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for JobId {
+    type Error = BbServiceError;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        if id.is_empty() {
+            return Err(BbServiceError::InvalidInput(
+                "job id must not be empty".to_string(),
+            ));
+        }
+        if id
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '/' | '\\' | '?' | '#' | '%'))
+        {
+            return Err(BbServiceError::InvalidInput(format!(
+                "job id {:?} contains characters not allowed in a path",
+                id
+            )));
+        }
+        if id == "." || id == ".." {
+            return Err(BbServiceError::InvalidInput(format!(
+                "job id {:?} is a relative path segment",
+                id
+            )));
+        }
+        Ok(JobId(id))
+    }
+}
+
+impl FromStr for JobId {
+    type Err = BbServiceError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        id.to_string().try_into()
+    }
+}
+
+impl From<JobId> for String {
+    fn from(job: JobId) -> Self {
+        job.0
+    }
+}
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// State of a proof job
+#[derive(Debug)]
+pub enum JobStatus {
+    /// Queued and waiting to start
+    Pending,
+    /// Currently being proved
+    Running,
+    /// Finished with a proof
+    Done(ProofData),
+    /// Finished without a proof, with the reason reported by the service
+    Failed(String),
+}
+
+impl JobStatus {
+    /// Whether the job has finished, successfully or not
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobStatus::Done(_) | JobStatus::Failed(_))
+    }
+}
+
+/// Response structure for proof job submission
+#[derive(Debug, Deserialize)]
+struct SubmitJobResponse {
+    #[serde(rename = "jobId")]
+    job_id: JobId,
+}
+
+/// Response structure for proof job polling
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum JobStatusResponse {
+    Pending,
+    Running,
+    Done { proof: ProofData },
+    Failed { error: Option<String> },
+}
+
+impl From<JobStatusResponse> for JobStatus {
+    fn from(response: JobStatusResponse) -> Self {
+        match response {
+            JobStatusResponse::Pending => JobStatus::Pending,
+            JobStatusResponse::Running => JobStatus::Running,
+            JobStatusResponse::Done { proof } => JobStatus::Done(proof),
+            JobStatusResponse::Failed { error } => {
+                JobStatus::Failed(error.unwrap_or_else(|| "unknown error".to_string()))
+            }
+        }
+    }
+}
+
+impl BbServiceClient {
+    /// Submit a proof job to the bb-service without waiting for the proof
+    ///
+    /// Poll the returned job with [`poll_proof_job`](Self::poll_proof_job) or
+    /// [`wait_for_proof`](Self::wait_for_proof).
+    pub async fn submit_proof_job(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+    ) -> Result<JobId, BbServiceError> {
+        let request = self.prove_request(circuit.borrow(), input)?;
+
+        let submit_response: SubmitJobResponse = self.post_json("/prove/jobs", &request).await?;
+        Ok(submit_response.job_id)
+    }
+
+    /// Fetch the current status of a proof job
+    pub async fn poll_proof_job(&self, job: &JobId) -> Result<JobStatus, BbServiceError> {
+        let status_response: JobStatusResponse =
+            self.get_json(&format!("/prove/jobs/{}", job)).await?;
+        Ok(status_response.into())
+    }
+
+    /// Poll a proof job every `poll_interval` until it finishes
+    ///
+    /// A failed job is returned as [`BbServiceError::JobFailed`].
+    pub async fn wait_for_proof(
+        &self,
+        job: &JobId,
+        poll_interval: Duration,
+    ) -> Result<ProofData, BbServiceError> {
+        loop {
+            match self.poll_proof_job(job).await? {
+                JobStatus::Done(proof) => return Ok(proof),
+                JobStatus::Failed(reason) => return Err(BbServiceError::JobFailed(reason)),
+                JobStatus::Pending | JobStatus::Running => platform::sleep(poll_interval).await,
+            }
+        }
+    }
+
+    /// Like [`wait_for_proof`](Self::wait_for_proof), but stop polling when
+    /// `token` is cancelled
+    ///
+    /// On cancellation the job is cancelled on the service with
+    /// [`cancel_proof_job`](Self::cancel_proof_job) on a best-effort basis,
+    /// and [`BbServiceError::Cancelled`] is returned either way.
+    pub async fn wait_for_proof_cancellable(
+        &self,
+        job: &JobId,
+        poll_interval: Duration,
+        token: CancellationToken,
+    ) -> Result<ProofData, BbServiceError> {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => {
+                let _ = self.cancel_proof_job(job).await;
+                Err(BbServiceError::Cancelled)
+            }
+            result = self.wait_for_proof(job, poll_interval) => result,
+        }
+    }
+
+    /// Ask the service to stop working on a proof job
+    pub async fn cancel_proof_job(&self, job: &JobId) -> Result<(), BbServiceError> {
+        let response = self
+            .send(self.request(Method::POST, &format!("/prove/jobs/{}/cancel", job)))
+            .await?;
+        if !response.status().is_success() {
+            return Err(self.service_error(response).await);
+        }
+        Ok(())
+    }
+}
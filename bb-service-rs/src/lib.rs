@@ -1,17 +1,275 @@
-use reqwest::{Client, Error as ReqwestError};
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs};
-use anyhow::Result;
+use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Error as ReqwestError, Method, RequestBuilder, Response, StatusCode};
+use body::JsonBody;
+use cache::VkCache;
+use retry::RetryPolicy;
+use base64::prelude::{BASE64_STANDARD, Engine as _};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
+
+mod abi;
+mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod body;
+mod builder;
+mod cache;
+mod capabilities;
+mod circuit;
+mod health;
+mod idempotency;
+mod idle;
+mod inputs;
+mod jobs;
+#[cfg(feature = "log")]
+mod logging;
+mod observer;
+mod platform;
+mod progress;
+mod proof;
+mod redact;
+mod request_id;
+mod retry;
+mod slot;
+mod streaming;
+#[cfg(feature = "tracing")]
+mod trace;
+#[cfg(not(target_arch = "wasm32"))]
+mod transport;
+
+pub use abi::{
+    AbiField, AbiParameter, AbiReturnType, AbiType, AbiVisibility, CircuitAbi, CompiledArtifact,
+    Sign, circuit_return_type, named_public_inputs, parse_artifact,
+};
+pub use builder::{BB_SERVICE_TOKEN_ENV, BB_SERVICE_URL_ENV, BbServiceClientBuilder};
+pub use cache::PreparedVerifier;
+pub use capabilities::ServiceCapabilities;
+pub use circuit::{
+    ValidationMode, check_compatibility, circuit_bytecode_bytes, circuit_hash,
+    circuit_noir_version, load_circuit_from_reader, parse_circuit_definition,
+    parse_circuit_definition_with,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use circuit::{
+    InvalidCircuitPolicy, load_circuit_definition, load_circuit_definition_blocking,
+    load_circuit_definition_with, load_circuits_from_dir,
+};
+pub use health::HealthStatus;
+pub use idempotency::IDEMPOTENCY_KEY_HEADER;
+pub use inputs::{InputBuilder, hex_to_field_value, to_field_value, validate_inputs};
+pub use jobs::{JobId, JobStatus};
+pub use observer::RequestObserver;
+pub use progress::ProofProgress;
+pub use request_id::REQUEST_ID_HEADER;
+pub use proof::{FIELD_BYTE_SIZE, ProofDataHex};
+pub use streaming::ProofMeta;
+pub use semver::VersionReq;
+pub use tokio_util::sync::CancellationToken;
 
 /// Error types for bb-service operations
 #[derive(Debug, thiserror::Error)]
 pub enum BbServiceError {
     #[error("Request failed: {0}")]
-    Request(#[from] ReqwestError),
-    #[error("Service error: {0}")]
-    Service(String),
+    Request(#[source] ReqwestError),
+    #[error("Request timed out{}: {source}", if *idle { " waiting for data (idle timeout)" } else { "" })]
+    Timeout {
+        source: ReqwestError,
+        /// Whether no bytes arrived for the
+        /// [idle timeout](BbServiceClientBuilder::idle_timeout), rather than
+        /// the whole request running out of time
+        idle: bool,
+    },
+    #[error("Failed to connect to service: {0}")]
+    Connection(#[source] ReqwestError),
+    #[error(
+        "Service error ({status}): {error}{}{}",
+        format_details(details),
+        format_request_id(request_id)
+    )]
+    Service {
+        status: u16,
+        error: String,
+        details: Option<String>,
+        /// Stable machine-readable code, on services that send one
+        code: Option<String>,
+        /// The response's `X-Request-Id`, on services that send one
+        request_id: Option<String>,
+    },
+    #[error("Rate limited by service (retry after: {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
     #[error("Invalid response format")]
     InvalidResponse,
+    #[error("Service returned status {status} with a body that could not be parsed ({length} bytes): {reason}")]
+    MalformedResponse {
+        status: u16,
+        length: usize,
+        reason: String,
+    },
+    #[error("Service returned a {status} response larger than the {limit} byte limit")]
+    ResponseTooLarge { status: u16, limit: usize },
+    #[error("Invalid base URL: {0}")]
+    InvalidUrl(String),
+    #[error("Batch item {index} failed: {source}")]
+    Batch {
+        index: usize,
+        #[source]
+        source: Box<BbServiceError>,
+    },
+    #[error("Proof job failed: {0}")]
+    JobFailed(String),
+    #[error("Invalid proof data: {0}")]
+    InvalidProof(String),
+    #[error("Invalid circuit inputs: {0}")]
+    InvalidInput(String),
+    #[error("Invalid circuit: {0}")]
+    Circuit(String),
+    #[error("Invalid client configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Service did not report its version")]
+    VersionUnknown,
+    #[error("Service version {actual} does not satisfy {expected}")]
+    VersionMismatch { expected: String, actual: String },
+    #[error("Request was cancelled")]
+    Cancelled,
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<ReqwestError> for BbServiceError {
+    fn from(err: ReqwestError) -> Self {
+        if err.is_timeout() {
+            BbServiceError::Timeout {
+                source: err,
+                idle: false,
+            }
+        } else if platform::is_connect(&err) {
+            BbServiceError::Connection(err)
+        } else {
+            BbServiceError::Request(err)
+        }
+    }
+}
+
+impl BbServiceError {
+    /// Whether the failure is likely transient and the request worth retrying
+    ///
+    /// True for timeouts, connection errors, rate limiting and 5xx responses,
+    /// false for 4xx responses and errors caused by the request or response
+    /// contents. Unlike the client's own retries, timeouts count as retryable
+    /// here, so callers running their own retry loop can decide whether a
+    /// slow request should be resent. A batch error takes the classification
+    /// of the item that failed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BbServiceError::Timeout { .. }
+            | BbServiceError::Connection(_)
+            | BbServiceError::RateLimited { .. } => true,
+            BbServiceError::Service { status, .. } => *status >= 500,
+            BbServiceError::Request(err) => RetryPolicy::should_retry_error(err),
+            BbServiceError::Batch { source, .. } => source.is_retryable(),
+            BbServiceError::InvalidResponse
+            | BbServiceError::MalformedResponse { .. }
+            | BbServiceError::ResponseTooLarge { .. }
+            | BbServiceError::JobFailed(_)
+            | BbServiceError::InvalidProof(_)
+            | BbServiceError::InvalidUrl(_)
+            | BbServiceError::InvalidInput(_)
+            | BbServiceError::Circuit(_)
+            | BbServiceError::InvalidConfig(_)
+            | BbServiceError::VersionUnknown
+            | BbServiceError::VersionMismatch { .. }
+            | BbServiceError::Cancelled
+            | BbServiceError::Io(_) => false,
+        }
+    }
+
+    /// The service's error code as an [`ErrorCode`], for service errors that
+    /// carry one
+    ///
+    /// Match on this rather than the error message, which may change between
+    /// service releases. A batch error gives the code of the item that failed.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            BbServiceError::Service { code, .. } => code.as_deref().map(ErrorCode::from),
+            BbServiceError::Batch { source, .. } => source.code(),
+            _ => None,
+        }
+    }
+
+    /// The request ID the service tagged its error response with, see
+    /// [`REQUEST_ID_HEADER`]
+    ///
+    /// Quote it when reporting a failure so it can be found in the service's
+    /// logs. A batch error gives the ID of the item that failed.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            BbServiceError::Service { request_id, .. } => request_id.as_deref(),
+            BbServiceError::Batch { source, .. } => source.request_id(),
+            _ => None,
+        }
+    }
+
+    /// Attach the request ID of the response a service error came from
+    fn with_request_id(mut self, id: Option<String>) -> Self {
+        if let BbServiceError::Service { request_id, .. } = &mut self {
+            *request_id = id;
+        }
+        self
+    }
+}
+
+/// Known values of the `code` field in the service's error responses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// `CIRCUIT_TOO_LARGE`, the circuit exceeds the service's size limit
+    CircuitTooLarge,
+    /// `INVALID_CIRCUIT`, the circuit artifact could not be loaded
+    InvalidCircuit,
+    /// `INVALID_WITNESS`, the inputs don't satisfy the circuit's constraints
+    InvalidWitness,
+    /// `INVALID_PROOF`, the proof bytes could not be read
+    InvalidProof,
+    /// `UNSUPPORTED_BACKEND`, the requested backend isn't available
+    UnsupportedBackend,
+    /// A code this version of the client doesn't know about
+    Other(String),
+}
+
+impl ErrorCode {
+    /// The code as sent by the service
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::CircuitTooLarge => "CIRCUIT_TOO_LARGE",
+            ErrorCode::InvalidCircuit => "INVALID_CIRCUIT",
+            ErrorCode::InvalidWitness => "INVALID_WITNESS",
+            ErrorCode::InvalidProof => "INVALID_PROOF",
+            ErrorCode::UnsupportedBackend => "UNSUPPORTED_BACKEND",
+            ErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for ErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "CIRCUIT_TOO_LARGE" => ErrorCode::CircuitTooLarge,
+            "INVALID_CIRCUIT" => ErrorCode::InvalidCircuit,
+            "INVALID_WITNESS" => ErrorCode::InvalidWitness,
+            "INVALID_PROOF" => ErrorCode::InvalidProof,
+            "UNSUPPORTED_BACKEND" => ErrorCode::UnsupportedBackend,
+            other => ErrorCode::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Represents a compiled Noir circuit as arbitrary JSON
@@ -21,25 +279,96 @@ pub type CompiledCircuit = serde_json::Value;
 pub type InputMap = HashMap<String, serde_json::Value>;
 
 /// Proof data structure
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// `Debug` and `Display` summarize the byte vectors instead of printing them
+/// whole, so proofs can be logged.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProofData {
     pub proof: Vec<u8>,
     #[serde(rename = "publicInputs")]
     pub public_inputs: Vec<u8>,
 }
 
+/// Proving system used by bb
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvingBackend {
+    UltraHonk,
+    UltraPlonk,
+}
+
+/// Hash used for the proof's Fiat-Shamir transcript
+///
+/// A proof must be verified with the oracle hash it was generated with.
+/// Keccak suits on-chain verifiers, Poseidon is cheaper to verify inside
+/// another circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OracleHash {
+    Keccak,
+    Poseidon,
+}
+
+/// Result of verifying a proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// The proof is valid for the circuit
+    Valid,
+    /// The proof was rejected, with the reason when the service gives one
+    Invalid { reason: Option<String> },
+}
+
+impl VerifyOutcome {
+    /// Whether the proof is valid
+    pub fn is_valid(&self) -> bool {
+        matches!(self, VerifyOutcome::Valid)
+    }
+}
+
+/// Proving settings sent along with prove and verify requests, each omitted
+/// when unset so the service picks its default
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+struct ProofOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend: Option<ProvingBackend>,
+    #[serde(rename = "oracleHash", skip_serializing_if = "Option::is_none")]
+    oracle_hash: Option<OracleHash>,
+}
+
 /// Request structure for proof generation
 #[derive(Debug, Serialize)]
-struct ProveRequest {
-    circuit: CompiledCircuit,
+struct ProveRequest<'a> {
+    circuit: &'a CompiledCircuit,
     input: InputMap,
+    #[serde(flatten)]
+    options: ProofOptions,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    recursive: bool,
+}
+
+/// Request structure for proof generation from a precomputed witness
+#[derive(Debug, Serialize)]
+struct ProveWitnessRequest<'a> {
+    circuit: &'a CompiledCircuit,
+    witness: Vec<u8>,
 }
 
 /// Request structure for proof verification  
 #[derive(Debug, Serialize)]
-struct VerifyRequest {
-    circuit: CompiledCircuit,
+struct VerifyRequest<'a> {
+    circuit: &'a CompiledCircuit,
     proof: ProofData,
+    #[serde(flatten)]
+    options: ProofOptions,
+}
+
+/// Request structure for proof verification against a verification key
+#[derive(Debug, Serialize)]
+struct VerifyWithVkRequest {
+    vk: Vec<u8>,
+    proof: ProofData,
+    #[serde(flatten)]
+    options: ProofOptions,
 }
 
 /// Response structure for proof generation
@@ -52,9 +381,73 @@ struct ProveResponse {
 /// Response structure for proof verification
 #[derive(Debug, Deserialize)]
 struct VerifyResponse {
+    #[allow(dead_code)]
     message: String,
     #[serde(rename = "isValid")]
     is_valid: bool,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl From<VerifyResponse> for VerifyOutcome {
+    fn from(response: VerifyResponse) -> Self {
+        if response.is_valid {
+            VerifyOutcome::Valid
+        } else {
+            VerifyOutcome::Invalid {
+                reason: response.reason,
+            }
+        }
+    }
+}
+
+/// Request structure for endpoints that only need the circuit
+#[derive(Debug, Serialize)]
+struct CircuitRequest<'a> {
+    circuit: &'a CompiledCircuit,
+}
+
+/// Response structure for fetching a verification key
+#[derive(Debug, Deserialize)]
+struct VkResponse {
+    #[allow(dead_code)]
+    message: String,
+    #[serde(deserialize_with = "deserialize_bytes")]
+    vk: Vec<u8>,
+}
+
+/// Response structure for witness generation
+#[derive(Debug, Deserialize)]
+struct WitnessResponse {
+    #[allow(dead_code)]
+    message: String,
+    #[serde(deserialize_with = "deserialize_bytes")]
+    witness: Vec<u8>,
+}
+
+/// Response structure for circuit gate counts
+#[derive(Debug, Deserialize)]
+struct GateCountResponse {
+    #[allow(dead_code)]
+    message: String,
+    #[serde(rename = "gateCount")]
+    gate_count: u64,
+}
+
+/// Request structure for Solidity verifier export
+#[derive(Debug, Serialize)]
+struct SolidityVerifierRequest<'a> {
+    circuit: &'a CompiledCircuit,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend: Option<ProvingBackend>,
+}
+
+/// Response structure for Solidity verifier export
+#[derive(Debug, Deserialize)]
+struct SolidityVerifierResponse {
+    #[allow(dead_code)]
+    message: String,
+    contract: String,
 }
 
 /// Error response structure
@@ -62,119 +455,1014 @@ struct VerifyResponse {
 struct ErrorResponse {
     error: String,
     details: Option<String>,
+    #[serde(default)]
+    code: Option<String>,
 }
 
 /// Client for interacting with the bb-service
+///
+/// Cloning is cheap: clones share the same connection pool and verification
+/// key cache, so hand each task its own clone rather than wrapping the client
+/// in an `Arc`.
+#[derive(Clone)]
 pub struct BbServiceClient {
     client: Client,
     base_url: String,
+    timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    auth: Option<(HeaderName, HeaderValue)>,
+    headers: HeaderMap,
+    retry: RetryPolicy,
+    strict_inputs: bool,
+    options: ProofOptions,
+    recursive: bool,
+    idempotency_keys: bool,
+    limits: BodyLimits,
+    observer: Option<observer::SharedObserver>,
+    limiter: Option<Arc<Semaphore>>,
+    vk_cache: VkCache,
+}
+
+impl Default for BbServiceClient {
+    /// A client for the service on localhost, see [`BbServiceClient::new_localhost`]
+    fn default() -> Self {
+        Self::new_localhost()
+    }
+}
+
+impl std::fmt::Debug for BbServiceClient {
+    /// Shows the configuration with credentials and sensitive headers masked
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BbServiceClient")
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("auth", &redact::Auth(&self.auth))
+            .field("headers", &redact::Headers(&self.headers))
+            .field("retry", &self.retry)
+            .field("strict_inputs", &self.strict_inputs)
+            .field("options", &self.options)
+            .field("recursive", &self.recursive)
+            .field("idempotency_keys", &self.idempotency_keys)
+            .field("limits", &self.limits)
+            .field("observer", &self.observer)
+            .field("limiter", &self.limiter)
+            .finish_non_exhaustive()
+    }
 }
 
 impl BbServiceClient {
     /// Create a new bb-service client
-    pub fn new(base_url: String) -> Self {
-        Self {
-            client: Client::new(),
-            base_url,
-        }
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base_url` is not a valid http(s) URL or the client cannot be
+    /// built, use [`BbServiceClient::try_new`] or [`BbServiceClient::builder`] to
+    /// handle the error instead.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::try_new(base_url).expect("failed to build bb-service client")
+    }
+
+    /// Create a new bb-service client, failing if `base_url` is not a valid http(s) URL
+    pub fn try_new(base_url: impl Into<String>) -> Result<Self, BbServiceError> {
+        Self::builder().base_url(base_url).build()
+    }
+
+    /// Create a new bb-service client whose requests fail once `timeout` has elapsed
+    ///
+    /// The timeout covers the whole request, from connecting until the response body
+    /// has been read, and applies to every endpoint. Proving large circuits can take
+    /// minutes, so pick a value with that in mind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base_url` is not a valid http(s) URL or the client cannot be
+    /// built, use [`BbServiceClient::builder`] to handle the error instead.
+    pub fn with_timeout(base_url: impl Into<String>, timeout: Duration) -> Self {
+        Self::builder()
+            .base_url(base_url)
+            .timeout(timeout)
+            .build()
+            .expect("failed to build bb-service client")
+    }
+
+    /// Create a new bb-service client that sends requests through an existing `reqwest::Client`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base_url` is not a valid http(s) URL or the client cannot be
+    /// built, use [`BbServiceClient::builder`] to handle the error instead.
+    pub fn with_client(client: Client, base_url: impl Into<String>) -> Self {
+        Self::builder()
+            .client(client)
+            .base_url(base_url)
+            .build()
+            .expect("failed to build bb-service client")
     }
 
     /// Create a new bb-service client with default localhost URL
     pub fn new_localhost() -> Self {
-        Self::new("http://localhost:3000".to_string())
+        Self::new_localhost_port(3000)
+    }
+
+    /// Create a new bb-service client for `http://localhost:{port}`
+    pub fn new_localhost_port(port: u16) -> Self {
+        Self::builder()
+            .base_url(format!("http://localhost:{}", port))
+            .build()
+            .expect("failed to build bb-service client")
+    }
+
+    /// Create a new bb-service client configured from `BB_SERVICE_URL` and
+    /// `BB_SERVICE_TOKEN`, see [`BbServiceClientBuilder::from_env`]
+    pub fn from_env() -> Result<Self, BbServiceError> {
+        BbServiceClientBuilder::from_env()?.build()
+    }
+
+    /// Create a builder for configuring a client
+    pub fn builder() -> BbServiceClientBuilder {
+        BbServiceClientBuilder::default()
+    }
+
+    /// The base URL requests are sent to, without a trailing slash
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Point the client at a different service URL, keeping its connection
+    /// pool and settings
+    ///
+    /// The URL is normalized and validated like in
+    /// [`BbServiceClientBuilder::build`], and left unchanged if invalid. Only
+    /// this client is affected, not clones made earlier.
+    pub fn set_base_url(&mut self, url: impl Into<String>) -> Result<(), BbServiceError> {
+        let base_url = builder::normalize_base_url(url.into());
+        builder::validate_base_url(&base_url)?;
+        self.base_url = base_url;
+        Ok(())
     }
 
     /// Generate a proof using the bb-service
+    ///
+    /// The circuit can be passed by value, by reference or as an `Arc`, so a
+    /// large artifact used for many proofs never has to be cloned.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                url = %format_args!("{}/prove", self.base_url),
+                status = tracing::field::Empty,
+                elapsed_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn generate_proof(
         &self,
-        circuit: CompiledCircuit,
+        circuit: impl Borrow<CompiledCircuit>,
         input: InputMap,
     ) -> Result<ProofData, BbServiceError> {
-        let request = ProveRequest { circuit, input };
-        
+        self.prove(circuit.borrow(), input).await
+    }
+
+    /// Generate a proof with `timeout` in place of the client's
+    /// [`timeout`](BbServiceClientBuilder::timeout) for this call only
+    ///
+    /// Lets one client serve both small circuits, which should fail fast, and
+    /// large ones that need a generous default. The timeout covers each
+    /// attempt like the client's own does.
+    pub async fn generate_proof_with_timeout(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+        timeout: Duration,
+    ) -> Result<ProofData, BbServiceError> {
+        let request = self.prove_request(circuit.borrow(), input)?;
         let response = self
-            .client
-            .post(&format!("{}/prove", self.base_url))
-            .json(&request)
-            .send()
+            .send(self.prove_post().timeout(timeout).json_body(&request)?)
             .await?;
+        let prove_response: ProveResponse = self.json_response(response).await?;
+        Ok(prove_response.proof)
+    }
 
-        if response.status().is_success() {
-            let prove_response: ProveResponse = response.json().await?;
-            Ok(prove_response.proof)
-        } else {
-            let error_response: ErrorResponse = response
-                .json()
-                .await
-                .map_err(|_| BbServiceError::InvalidResponse)?;
-            Err(BbServiceError::Service(format!(
-                "{}: {}",
-                error_response.error,
-                error_response.details.unwrap_or_default()
-            )))
+    /// Generate a proof, returning the service's message along with it
+    ///
+    /// Some services put details such as the proving time or the backend
+    /// used in the message, which [`generate_proof`](Self::generate_proof)
+    /// drops. Its wording isn't stable, so show it rather than parse it.
+    pub async fn generate_proof_with_message(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+    ) -> Result<(ProofData, String), BbServiceError> {
+        let prove_response = self.prove_response(circuit.borrow(), input).await?;
+        Ok((prove_response.proof, prove_response.message))
+    }
+
+    /// Send a prove request and return the service's response without
+    /// reading it
+    ///
+    /// An escape hatch for callers that need the status or headers, e.g.
+    /// rate-limit counters, that [`generate_proof`](Self::generate_proof)
+    /// discards. The request is built and retried like there, but error
+    /// statuses are returned as responses too, except 429 which is still
+    /// [`BbServiceError::RateLimited`]. A successful body is the JSON object
+    /// `generate_proof` parses, with the proof under `proof`.
+    pub async fn prove_raw(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+    ) -> Result<Response, BbServiceError> {
+        self.send_prove(circuit.borrow(), input).await
+    }
+
+    /// Generate a proof with the given backend, overriding the client's
+    ///
+    /// Verify the proof with the same backend, e.g. with
+    /// [`verify_proof_with_backend`](Self::verify_proof_with_backend), since
+    /// proof formats differ between backends.
+    pub async fn generate_proof_with_backend(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+        backend: ProvingBackend,
+    ) -> Result<ProofData, BbServiceError> {
+        let mut request = self.prove_request(circuit.borrow(), input)?;
+        request.options.backend = Some(backend);
+
+        let response = self.send(self.prove_post().json_body(&request)?).await?;
+        let prove_response: ProveResponse = self.json_response(response).await?;
+        Ok(prove_response.proof)
+    }
+
+    /// Generate a proof, aborting with [`BbServiceError::Cancelled`] as soon as
+    /// `token` is cancelled
+    ///
+    /// Cancelling drops the in-flight request, the service may still finish
+    /// the proof. Use [`wait_for_proof_cancellable`](Self::wait_for_proof_cancellable)
+    /// with a proof job to also cancel the work on the service.
+    pub async fn generate_proof_cancellable(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+        token: CancellationToken,
+    ) -> Result<ProofData, BbServiceError> {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(BbServiceError::Cancelled),
+            result = self.prove(circuit.borrow(), input) => result,
         }
     }
 
+    /// Execute a circuit and return the serialized witness using the bb-service
+    pub async fn generate_witness(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+    ) -> Result<Vec<u8>, BbServiceError> {
+        let request = self.prove_request(circuit.borrow(), input)?;
+
+        let witness_response: WitnessResponse = self.post_json("/execute", &request).await?;
+        Ok(witness_response.witness)
+    }
+
+    /// Generate a proof from a witness produced by
+    /// [`generate_witness`](Self::generate_witness), skipping circuit execution
+    pub async fn generate_proof_from_witness(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        witness: Vec<u8>,
+    ) -> Result<ProofData, BbServiceError> {
+        let request = ProveWitnessRequest {
+            circuit: circuit.borrow(),
+            witness,
+        };
+
+        let prove_response: ProveResponse = self.post_json("/prove/witness", &request).await?;
+        Ok(prove_response.proof)
+    }
+
     /// Verify a proof using the bb-service
+    ///
+    /// See [`verify_proof_detailed`](Self::verify_proof_detailed) for the reason
+    /// an invalid proof was rejected.
     pub async fn verify_proof(
         &self,
-        circuit: CompiledCircuit,
+        circuit: impl Borrow<CompiledCircuit>,
+        proof: ProofData,
+    ) -> Result<bool, BbServiceError> {
+        Ok(self.verify_proof_detailed(circuit, proof).await?.is_valid())
+    }
+
+    /// Generate a proof and verify it against the same circuit, returning the
+    /// proof and whether it verified
+    ///
+    /// Handy for smoke tests of a circuit. Errors from either request are
+    /// returned as is, so an `Ok((_, false))` always means the service
+    /// rejected its own proof.
+    pub async fn prove_and_verify(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+    ) -> Result<(ProofData, bool), BbServiceError> {
+        let circuit = circuit.borrow();
+        let proof = self.generate_proof(circuit, input).await?;
+        let is_valid = self.verify_proof(circuit, proof.clone()).await?;
+        Ok((proof, is_valid))
+    }
+
+    /// Verify a proof using the bb-service, returning why it was rejected if
+    /// the service reports a `reason`
+    ///
+    /// The reason helps tell a bad proof apart from a proof made for a
+    /// different circuit or verification key. A proof with no proof bytes is
+    /// rejected with [`BbServiceError::InvalidProof`] without a round trip,
+    /// empty public inputs are sent as is.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            err,
+            fields(
+                url = %format_args!("{}/verify", self.base_url),
+                status = tracing::field::Empty,
+                elapsed_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn verify_proof_detailed(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        proof: ProofData,
+    ) -> Result<VerifyOutcome, BbServiceError> {
+        self.verify(VerifyRequest {
+            circuit: circuit.borrow(),
+            proof,
+            options: self.options,
+        })
+        .await
+    }
+
+    /// Verify a proof with `timeout` in place of the client's
+    /// [`timeout`](BbServiceClientBuilder::timeout) for this call only, see
+    /// [`generate_proof_with_timeout`](Self::generate_proof_with_timeout)
+    pub async fn verify_proof_with_timeout(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
         proof: ProofData,
+        timeout: Duration,
     ) -> Result<bool, BbServiceError> {
-        let request = VerifyRequest { circuit, proof };
-        
+        let request = VerifyRequest {
+            circuit: circuit.borrow(),
+            proof,
+            options: self.options,
+        };
+        proof::check_verifiable(&request.proof)?;
         let response = self
-            .client
-            .post(&format!("{}/verify", self.base_url))
-            .json(&request)
-            .send()
+            .send(
+                self.request(Method::POST, "/verify")
+                    .timeout(timeout)
+                    .json_body(&request)?,
+            )
+            .await?;
+        let verify_response: VerifyResponse = self.json_response(response).await?;
+        Ok(verify_response.is_valid)
+    }
+
+    /// Send a verify request and return the service's response without
+    /// reading it
+    ///
+    /// The counterpart of [`prove_raw`](Self::prove_raw) for
+    /// [`verify_proof`](Self::verify_proof). A proof with no proof bytes is
+    /// still rejected without a round trip.
+    pub async fn verify_raw(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        proof: ProofData,
+    ) -> Result<Response, BbServiceError> {
+        self.send_verify(&VerifyRequest {
+            circuit: circuit.borrow(),
+            proof,
+            options: self.options,
+        })
+        .await
+    }
+
+    /// Verify a proof made with
+    /// [`generate_proof_with_backend`](Self::generate_proof_with_backend)
+    /// using the same backend
+    pub async fn verify_proof_with_backend(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        proof: ProofData,
+        backend: ProvingBackend,
+    ) -> Result<bool, BbServiceError> {
+        let mut options = self.options;
+        options.backend = Some(backend);
+        let outcome = self
+            .verify(VerifyRequest {
+                circuit: circuit.borrow(),
+                proof,
+                options,
+            })
+            .await?;
+        Ok(outcome.is_valid())
+    }
+
+    /// Verify a proof against a verification key using the bb-service
+    ///
+    /// Sends only the key instead of the whole circuit, pair it with
+    /// [`get_verification_key`](Self::get_verification_key) when verifying many
+    /// proofs for the same circuit. An empty proof is rejected like in
+    /// [`verify_proof_detailed`](Self::verify_proof_detailed).
+    pub async fn verify_proof_with_vk(
+        &self,
+        vk: Vec<u8>,
+        proof: ProofData,
+    ) -> Result<bool, BbServiceError> {
+        proof::check_verifiable(&proof)?;
+        let request = VerifyWithVkRequest {
+            vk,
+            proof,
+            options: self.options,
+        };
+
+        let verify_response: VerifyResponse = self.post_json("/verify/vk", &request).await?;
+        Ok(verify_response.is_valid)
+    }
+
+    /// Fetch the verification key for a circuit using the bb-service
+    pub async fn get_verification_key(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+    ) -> Result<Vec<u8>, BbServiceError> {
+        let request = CircuitRequest {
+            circuit: circuit.borrow(),
+        };
+
+        let vk_response: VkResponse = self.post_json("/vk", &request).await?;
+        Ok(vk_response.vk)
+    }
+
+    /// Get the gate count of a circuit using the bb-service
+    ///
+    /// Useful for estimating proving cost, or rejecting oversized circuits,
+    /// before calling [`generate_proof`](Self::generate_proof).
+    pub async fn get_gate_count(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+    ) -> Result<u64, BbServiceError> {
+        let request = CircuitRequest {
+            circuit: circuit.borrow(),
+        };
+
+        let gate_count_response: GateCountResponse = self.post_json("/gates", &request).await?;
+        Ok(gate_count_response.gate_count)
+    }
+
+    /// Have the service load a circuit ahead of real traffic
+    ///
+    /// The first proof for a circuit is slow while the service compiles it and
+    /// loads the CRS, so call this at startup to keep later latencies
+    /// predictable. Posts the circuit to `/warmup`, and falls back to
+    /// [`get_gate_count`](Self::get_gate_count), which loads the circuit as a
+    /// side effect, on servers without that endpoint (404 or 405).
+    pub async fn warmup_circuit(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+    ) -> Result<(), BbServiceError> {
+        let request = CircuitRequest {
+            circuit: circuit.borrow(),
+        };
+
+        let response = self
+            .send(self.request(Method::POST, "/warmup").json_body(&request)?)
             .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        if batch::is_unsupported(status.as_u16()) {
+            return self.get_gate_count(request.circuit).await.map(|_| ());
+        }
+        Err(self.service_error(response).await)
+    }
 
-        if response.status().is_success() {
-            let verify_response: VerifyResponse = response.json().await?;
-            Ok(verify_response.is_valid)
+    /// Export the Solidity verifier contract for a circuit using the bb-service
+    ///
+    /// Uses the client's backend if one is set, otherwise the service picks the
+    /// verifier flavour. Use
+    /// [`export_solidity_verifier_with_backend`](Self::export_solidity_verifier_with_backend)
+    /// to ask for a specific one.
+    pub async fn export_solidity_verifier(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+    ) -> Result<String, BbServiceError> {
+        self.solidity_verifier(circuit.borrow(), self.options.backend).await
+    }
+
+    /// Export the Solidity verifier contract for a circuit for the given backend,
+    /// since Honk and Plonk verifiers differ
+    pub async fn export_solidity_verifier_with_backend(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        backend: ProvingBackend,
+    ) -> Result<String, BbServiceError> {
+        self.solidity_verifier(circuit.borrow(), Some(backend)).await
+    }
+
+    /// Request the Solidity verifier with an optional backend hint
+    async fn solidity_verifier(
+        &self,
+        circuit: &CompiledCircuit,
+        backend: Option<ProvingBackend>,
+    ) -> Result<String, BbServiceError> {
+        let request = SolidityVerifierRequest { circuit, backend };
+
+        let verifier_response: SolidityVerifierResponse =
+            self.post_json("/solidity-verifier", &request).await?;
+        Ok(verifier_response.contract)
+    }
+
+    /// Generate a proof without taking ownership of the circuit
+    async fn prove(
+        &self,
+        circuit: &CompiledCircuit,
+        input: InputMap,
+    ) -> Result<ProofData, BbServiceError> {
+        Ok(self.prove_response(circuit, input).await?.proof)
+    }
+
+    /// Generate a proof, keeping the service's message
+    async fn prove_response(
+        &self,
+        circuit: &CompiledCircuit,
+        input: InputMap,
+    ) -> Result<ProveResponse, BbServiceError> {
+        let response = self.send_prove(circuit, input).await?;
+        self.json_response(response).await
+    }
+
+    /// Send a prove request with the client's proving options
+    async fn send_prove(
+        &self,
+        circuit: &CompiledCircuit,
+        input: InputMap,
+    ) -> Result<Response, BbServiceError> {
+        let request = self.prove_request(circuit, input)?;
+        self.send(self.prove_post().json_body(&request)?).await
+    }
+
+    /// Send a verify request and parse the outcome
+    async fn verify(&self, request: VerifyRequest<'_>) -> Result<VerifyOutcome, BbServiceError> {
+        let response = self.send_verify(&request).await?;
+        let verify_response: VerifyResponse = self.json_response(response).await?;
+        Ok(verify_response.into())
+    }
+
+    /// Send a verify request, rejecting empty proofs first
+    async fn send_verify(&self, request: &VerifyRequest<'_>) -> Result<Response, BbServiceError> {
+        proof::check_verifiable(&request.proof)?;
+        self.send(self.request(Method::POST, "/verify").json_body(request)?)
+            .await
+    }
+
+    /// Build a prove request with the client's proving options, checking the
+    /// inputs first if `strict_inputs` is set
+    fn prove_request<'a>(
+        &self,
+        circuit: &'a CompiledCircuit,
+        input: InputMap,
+    ) -> Result<ProveRequest<'a>, BbServiceError> {
+        if self.strict_inputs {
+            validate_inputs(circuit, &input)?;
+        }
+        Ok(ProveRequest {
+            circuit,
+            input,
+            options: self.options,
+            recursive: self.recursive,
+        })
+    }
+
+    /// POST `body` as JSON to `path` and parse the JSON response on success
+    async fn post_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, BbServiceError> {
+        let response = self
+            .send(self.request(Method::POST, path).json_body(body)?)
+            .await?;
+        self.json_response(response).await
+    }
+
+    /// GET `path` and parse the JSON response on success
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, BbServiceError> {
+        let response = self.send(self.request(Method::GET, path)).await?;
+        self.json_response(response).await
+    }
+
+    /// Parse a JSON success body, or the service's error for other statuses
+    async fn json_response<T: DeserializeOwned>(
+        &self,
+        response: Response,
+    ) -> Result<T, BbServiceError> {
+        let status = response.status();
+        if status.is_success() {
+            let content_type = content_type(response.headers());
+            let body = self.read_body(response).await?;
+            parse_success_body(status, content_type.as_deref(), &body)
         } else {
-            let error_response: ErrorResponse = response
-                .json()
+            Err(self.service_error(response).await)
+        }
+    }
+
+    /// Build the error for a non-success response from the service's error body
+    ///
+    /// Bodies that aren't a JSON `ErrorResponse` (e.g. an HTML page from a
+    /// proxy) are kept as the details. Error and details text are truncated to
+    /// the client's error length limit.
+    async fn service_error(&self, response: Response) -> BbServiceError {
+        let status = response.status();
+        let request_id = request_id::from_headers(response.headers());
+        match self.read_body(response).await {
+            Ok(body) => service_error_from_body(
+                status,
+                &String::from_utf8_lossy(&body),
+                self.limits.max_error_len,
+            )
+            .with_request_id(request_id),
+            Err(err) => err,
+        }
+    }
+
+    /// Read a whole response body, failing once it exceeds the client's
+    /// response size limit
+    async fn read_body(&self, response: Response) -> Result<Vec<u8>, BbServiceError> {
+        let status = response.status();
+        let sent = idle::sent_at(&response);
+        let _slot = slot::of(&response);
+        let Some(limit) = self.limits.max_response_size else {
+            return Ok(response
+                .bytes()
                 .await
-                .map_err(|_| BbServiceError::InvalidResponse)?;
-            Err(BbServiceError::Service(format!(
-                "{}: {}",
-                error_response.error,
-                error_response.details.unwrap_or_default()
-            )))
+                .map_err(|err| self.request_error(err, sent))?
+                .to_vec());
+        };
+        let too_large = BbServiceError::ResponseTooLarge {
+            status: status.as_u16(),
+            limit,
+        };
+        if response
+            .content_length()
+            .is_some_and(|length| length > limit as u64)
+        {
+            return Err(too_large);
         }
+
+        let mut body = Vec::new();
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks
+            .next()
+            .await
+            .transpose()
+            .map_err(|err| self.request_error(err, sent))?
+        {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large);
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
     }
 
-    /// Check if the bb-service is healthy/reachable
-    pub async fn health_check(&self) -> Result<bool, BbServiceError> {
-        let response = self
+    /// Send a request, retrying according to the client's retry policy and
+    /// reporting it to the client's observer
+    ///
+    /// A 429 response is retried after the delay given by its `Retry-After`
+    /// header, and surfaces as [`BbServiceError::RateLimited`] once retries
+    /// are exhausted.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, BbServiceError> {
+        let Some(observer::SharedObserver(observer)) = &self.observer else {
+            return self.send_with_retries(request).await;
+        };
+
+        let started = platform::Instant::now();
+        observer.on_start();
+        let result = self.send_with_retries(request).await;
+        match &result {
+            Ok(response) => observer.on_success(response.status(), started.elapsed()),
+            Err(err) => observer.on_error(err),
+        }
+        result
+    }
+
+    /// The retry loop of [`send`](Self::send)
+    async fn send_with_retries(&self, request: RequestBuilder) -> Result<Response, BbServiceError> {
+        let started = platform::Instant::now();
+        let mut attempt = 0;
+        loop {
+            // Bodies are always buffered JSON, so cloning only fails for streams
+            let Some(current) = request.try_clone() else {
+                let (sent, send) = start_send(request);
+                let result = self.throttled(send).await;
+                #[cfg(feature = "tracing")]
+                trace::record_response(&result, started);
+                #[cfg(feature = "log")]
+                logging::finished(&result);
+                return rate_limit_error(self.with_sent_time(result, sent)?);
+            };
+            let (sent, send) = start_send(current);
+            let result = self.throttled(send).await;
+
+            let retry_after = result
+                .as_ref()
+                .ok()
+                .and_then(|response| retry::retry_after(response.headers()));
+            let delay = retry_after.unwrap_or_else(|| self.retry.backoff(attempt));
+            if attempt >= self.retry.max_retries
+                || !RetryPolicy::should_retry(&result)
+                || !self.retry.accepts_retry_after(retry_after)
+                || !self.retry.within_deadline(started.elapsed(), delay)
+            {
+                #[cfg(feature = "tracing")]
+                trace::record_response(&result, started);
+                #[cfg(feature = "log")]
+                logging::finished(&result);
+                return rate_limit_error(self.with_sent_time(result, sent)?);
+            }
+            #[cfg(feature = "log")]
+            logging::retrying(&result, delay, attempt);
+            // Give up the slot while waiting
+            drop(result);
+            platform::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Note on the response how its request was `sent`, or convert the error
+    fn with_sent_time(
+        &self,
+        result: Result<Response, ReqwestError>,
+        sent: idle::Sent,
+    ) -> Result<Response, BbServiceError> {
+        match result {
+            Ok(mut response) => {
+                idle::mark_sent(&mut response, sent);
+                Ok(response)
+            }
+            Err(err) => Err(self.request_error(err, Some(sent))),
+        }
+    }
+
+    /// Run `send` once a slot is free, if the client limits concurrent requests
+    ///
+    /// The slot stays with the response until its body has been read, see
+    /// [`slot`].
+    async fn throttled(
+        &self,
+        send: impl Future<Output = Result<Response, ReqwestError>>,
+    ) -> Result<Response, ReqwestError> {
+        let permit = match &self.limiter {
+            Some(limiter) => Some(
+                limiter
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("limiter is never closed"),
+            ),
+            None => None,
+        };
+        let mut response = send.await?;
+        if let Some(permit) = permit {
+            slot::hold(&mut response, permit);
+        }
+        Ok(response)
+    }
+
+    /// Start a request to `path` on the service with the client's settings applied
+    ///
+    /// Asks for JSON unless the client's headers set their own `Accept`.
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.request_url(method, &format!("{}{}", self.base_url, path));
+        if self.headers.contains_key(ACCEPT) {
+            request
+        } else {
+            request.header(ACCEPT, HeaderValue::from_static(JSON))
+        }
+    }
+
+    /// Start a `POST /prove` request, carrying a fresh idempotency key if
+    /// enabled
+    ///
+    /// The key is set once here, so retries of the request reuse it.
+    fn prove_post(&self) -> RequestBuilder {
+        let request = self.request(Method::POST, "/prove");
+        if self.idempotency_keys {
+            request.header(IDEMPOTENCY_KEY_HEADER, idempotency::new_key())
+        } else {
+            request
+        }
+    }
+
+    /// Start a request to an absolute `url` with the client's settings applied
+    fn request_url(&self, method: Method, url: &str) -> RequestBuilder {
+        #[cfg(feature = "log")]
+        logging::sending(&method, url);
+        let request = self
             .client
-            .get(&format!("{}/health", self.base_url))
-            .send()
-            .await?;
-        
-        Ok(response.status().is_success())
-    }
-}
-
-pub async fn load_circuit_definition(path: &str) -> Result<CompiledCircuit> {
-    let circuit_content = fs::read_to_string(path)
-        .map_err(|e| anyhow::anyhow!("Failed to read circuit file {}: {}", path, e))?;
-    
-    let circuit_json: serde_json::Value = serde_json::from_str(&circuit_content)
-        .map_err(|e| anyhow::anyhow!("Failed to parse circuit JSON: {}", e))?;
-    
-    // Validate that it contains the essential fields
-    if !circuit_json.is_object() {
-        return Err(anyhow::anyhow!("Circuit JSON must be an object"));
-    }
-    
-    let obj = circuit_json.as_object().unwrap();
-    if !obj.contains_key("bytecode") || !obj.contains_key("abi") {
-        return Err(anyhow::anyhow!("Circuit JSON must contain 'bytecode' and 'abi' fields"));
-    }
-    
-    // Return the entire JSON object as-is
-    Ok(circuit_json)
-}
\ No newline at end of file
+            .request(method, url)
+            .headers(self.headers.clone());
+        let request = match &self.auth {
+            Some((name, value)) => request.header(name, value),
+            None => request,
+        };
+        match self.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        }
+    }
+}
+
+/// Deserialize bytes sent either as a JSON byte array or a base64 string
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Bytes {
+        Array(Vec<u8>),
+        Base64(String),
+    }
+
+    match Bytes::deserialize(deserializer)? {
+        Bytes::Array(bytes) => Ok(bytes),
+        Bytes::Base64(encoded) => BASE64_STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Media type of the service's request and response bodies
+const JSON: &str = "application/json";
+
+/// The `Content-Type` of a response, if it has a readable one
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Whether a `Content-Type` is JSON, `application/json` or a `+json` type
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == JSON || essence.ends_with("+json")
+}
+
+/// Parse the JSON body of a success response
+///
+/// An empty or truncated body, e.g. from a proxy dropping the connection, is
+/// reported as [`BbServiceError::MalformedResponse`] with the status and body
+/// length rather than as a bare JSON error. So is a body whose `Content-Type`
+/// isn't JSON, such as a proxy's HTML login page, without trying to parse it.
+/// A missing `Content-Type` is tolerated.
+fn parse_success_body<T: DeserializeOwned>(
+    status: StatusCode,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<T, BbServiceError> {
+    let reason = if let Some(content_type) = content_type.filter(|value| !is_json(value)) {
+        format!("expected a JSON body, got {}", content_type)
+    } else if body.iter().all(u8::is_ascii_whitespace) {
+        "empty body".to_string()
+    } else {
+        match serde_json::from_slice(body) {
+            Ok(value) => return Ok(value),
+            Err(e) => e.to_string(),
+        }
+    };
+    Err(BbServiceError::MalformedResponse {
+        status: status.as_u16(),
+        length: body.len(),
+        reason,
+    })
+}
+
+/// Default number of bytes of error and details text kept in
+/// [`BbServiceError::Service`]
+pub const DEFAULT_MAX_ERROR_LEN: usize = 4 * 1024;
+
+/// How much of a response body the client keeps
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BodyLimits {
+    /// Bytes of error and details text kept in service errors
+    pub(crate) max_error_len: usize,
+    /// Largest body read into memory, unlimited when `None`
+    pub(crate) max_response_size: Option<usize>,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            max_error_len: DEFAULT_MAX_ERROR_LEN,
+            max_response_size: None,
+        }
+    }
+}
+
+/// Build the error for a non-success status from the already-read error body,
+/// truncating its text to `max_len` bytes
+fn service_error_from_body(status: StatusCode, body: &str, max_len: usize) -> BbServiceError {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(error_response) => BbServiceError::Service {
+            status: status.as_u16(),
+            error: truncate(&error_response.error, max_len),
+            details: error_response
+                .details
+                .map(|details| truncate(&details, max_len)),
+            code: error_response.code,
+            request_id: None,
+        },
+        Err(_) => BbServiceError::Service {
+            status: status.as_u16(),
+            error: status
+                .canonical_reason()
+                .unwrap_or("Unexpected response")
+                .to_string(),
+            details: (!body.trim().is_empty()).then(|| truncate(body, max_len)),
+            code: None,
+            request_id: None,
+        },
+    }
+}
+
+/// Truncate `text` to at most `max_len` bytes on a char boundary, marking the cut
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text.to_string();
+    }
+    let mut end = max_len;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &text[..end])
+}
+
+/// Render an optional request ID as a ` (request id ...)` suffix
+fn format_request_id(request_id: &Option<String>) -> String {
+    request_id
+        .as_deref()
+        .map(|id| format!(" (request id {})", id))
+        .unwrap_or_default()
+}
+
+/// Render optional error details as a `: details` suffix
+fn format_details(details: &Option<String>) -> String {
+    details
+        .as_deref()
+        .map(|details| format!(": {}", details))
+        .unwrap_or_default()
+}
+
+/// Start sending `request`, noting when and with which timeout
+///
+/// reqwest starts the request's timers right away, before the future is
+/// first polled, so `sent` matches them even while waiting for a slot.
+fn start_send(
+    request: RequestBuilder,
+) -> (
+    idle::Sent,
+    impl Future<Output = Result<Response, ReqwestError>>,
+) {
+    let (client, request) = request.build_split();
+    let sent = idle::Sent {
+        at: platform::Instant::now(),
+        timeout: request
+            .as_ref()
+            .ok()
+            .and_then(|request| request.timeout().copied()),
+    };
+    let send = request.map(|request| client.execute(request));
+    (sent, async move { send?.await })
+}
+
+/// Turn a 429 response into [`BbServiceError::RateLimited`]
+fn rate_limit_error(response: Response) -> Result<Response, BbServiceError> {
+    match rate_limited(response.status(), response.headers()) {
+        Some(err) => Err(err),
+        None => Ok(response),
+    }
+}
+
+/// The [`BbServiceError::RateLimited`] error for a 429 status, if it is one
+fn rate_limited(status: StatusCode, headers: &HeaderMap) -> Option<BbServiceError> {
+    (status == StatusCode::TOO_MANY_REQUESTS).then(|| BbServiceError::RateLimited {
+        retry_after: retry::retry_after(headers),
+    })
+}
@@ -0,0 +1,91 @@
+//! `log` output for the `log` feature
+//!
+//! Only methods, URLs and statuses are logged, never bodies or headers, and
+//! URLs lose their userinfo and query string so credentials embedded in them
+//! stay out of the logs too.
+
+use crate::{platform, redact};
+use reqwest::{Error as ReqwestError, Method, StatusCode, Url};
+use std::time::Duration;
+
+/// What the log lines need from either client's response
+pub(crate) trait Answer {
+    fn status(&self) -> StatusCode;
+    fn url(&self) -> &Url;
+}
+
+impl Answer for reqwest::Response {
+    fn status(&self) -> StatusCode {
+        reqwest::Response::status(self)
+    }
+
+    fn url(&self) -> &Url {
+        reqwest::Response::url(self)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Answer for reqwest::blocking::Response {
+    fn status(&self) -> StatusCode {
+        reqwest::blocking::Response::status(self)
+    }
+
+    fn url(&self) -> &Url {
+        reqwest::blocking::Response::url(self)
+    }
+}
+
+/// Log a request about to be made, at debug
+pub(crate) fn sending(method: &Method, url: &str) {
+    let url = Url::parse(url).map_or_else(|_| "<invalid URL>".to_string(), |url| redact::url(&url));
+    log::debug!("Sending {} {}", method, url);
+}
+
+/// Log a retry, at debug
+pub(crate) fn retrying<R: Answer>(result: &Result<R, ReqwestError>, delay: Duration, attempt: u32) {
+    log::debug!(
+        "Retrying {} in {:?} after {} (retry {})",
+        url(result),
+        delay,
+        describe(result),
+        attempt + 1
+    );
+}
+
+/// Log the final attempt at warn if it failed
+pub(crate) fn finished<R: Answer>(result: &Result<R, ReqwestError>) {
+    if result
+        .as_ref()
+        .is_ok_and(|response| response.status().is_success())
+    {
+        return;
+    }
+    log::warn!(
+        "Request to {} failed with {}",
+        url(result),
+        describe(result)
+    );
+}
+
+/// The redacted URL of a response or failed request
+fn url<R: Answer>(result: &Result<R, ReqwestError>) -> String {
+    let url = match result {
+        Ok(response) => Some(response.url()),
+        Err(err) => err.url(),
+    };
+    url.map_or_else(|| "<unknown URL>".to_string(), redact::url)
+}
+
+/// A short description of how a request ended, without the URL that
+/// reqwest's errors include
+fn describe<R: Answer>(result: &Result<R, ReqwestError>) -> String {
+    match result {
+        Ok(response) => format!("status {}", response.status()),
+        Err(err) if err.is_timeout() => "a timeout".to_string(),
+        Err(err) if platform::is_connect(err) => "a connection error".to_string(),
+        Err(err) => match std::error::Error::source(err) {
+            Some(source) => format!("an error: {}", source),
+            None => "an error".to_string(),
+        },
+    }
+}
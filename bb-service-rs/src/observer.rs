@@ -0,0 +1,40 @@
+use crate::BbServiceError;
+use reqwest::StatusCode;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Hooks called around every request the client makes, for emitting metrics
+/// with whatever library the application uses
+///
+/// Set with [`BbServiceClientBuilder::observer`](crate::BbServiceClientBuilder::observer).
+/// A request covers all of its retries, so each starts once and then either
+/// succeeds or fails once. Every method defaults to doing nothing.
+pub trait RequestObserver: Send + Sync {
+    /// A request is about to be sent
+    fn on_start(&self) {}
+
+    /// The service answered with `status` after `elapsed`
+    ///
+    /// Called for error statuses too, other than 429, since the service still
+    /// answered.
+    fn on_success(&self, status: StatusCode, elapsed: Duration) {
+        let _ = (status, elapsed);
+    }
+
+    /// The request failed without an answer from the service, or was rate
+    /// limited
+    fn on_error(&self, error: &BbServiceError) {
+        let _ = error;
+    }
+}
+
+/// A [`RequestObserver`] shared by a client and its clones
+#[derive(Clone)]
+pub(crate) struct SharedObserver(pub(crate) Arc<dyn RequestObserver>);
+
+impl fmt::Debug for SharedObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestObserver")
+    }
+}
@@ -0,0 +1,39 @@
+//! Small shims over the parts of the runtime that differ on `wasm32`, where
+//! there is no tokio timer and `std::time` clocks panic.
+
+use reqwest::Error as ReqwestError;
+use std::time::Duration;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) use web_time::Instant;
+
+/// Wait for `duration` without blocking the executor
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Time left until `time`, zero if it has passed
+pub(crate) fn until(time: std::time::SystemTime) -> Duration {
+    let target = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO);
+    target.saturating_sub(now)
+}
+
+/// Whether the request failed while connecting, which the browser's fetch
+/// API doesn't report separately
+pub(crate) fn is_connect(err: &ReqwestError) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    return err.is_connect();
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = err;
+        false
+    }
+}
@@ -0,0 +1,208 @@
+use crate::body::JsonBody;
+use crate::{
+    BbServiceClient, BbServiceError, CompiledCircuit, InputMap, ProofData, ProveResponse, idle,
+    request_id, service_error_from_body, slot,
+};
+use futures::StreamExt;
+use reqwest::StatusCode;
+use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue};
+use serde::Deserialize;
+use std::borrow::Borrow;
+
+/// Content type of a server-sent event stream
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// A progress update for a proof being generated
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProofProgress {
+    /// Stage label, e.g. `witness` or `proving`
+    pub stage: String,
+    /// Completion of the stage or of the whole proof, from 0 to 100
+    pub percent: Option<f32>,
+}
+
+impl ProofProgress {
+    /// Stage reported before the request is sent
+    pub const STARTED: &'static str = "started";
+    /// Stage reported once the proof has been received
+    pub const FINISHED: &'static str = "finished";
+
+    fn stage(stage: &str, percent: Option<f32>) -> Self {
+        Self {
+            stage: stage.to_string(),
+            percent,
+        }
+    }
+}
+
+impl BbServiceClient {
+    /// Generate a proof, reporting progress to `on_progress` as it happens
+    ///
+    /// The callback always sees a [`STARTED`](ProofProgress::STARTED) update
+    /// first and a [`FINISHED`](ProofProgress::FINISHED) update once the proof
+    /// arrives. When the service streams server-sent events, each `progress`
+    /// event is reported in between. An `error` event is returned as a 500
+    /// [`BbServiceError::Service`] since the response status has already been
+    /// sent by then.
+    pub async fn generate_proof_with_progress(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+        on_progress: impl Fn(ProofProgress),
+    ) -> Result<ProofData, BbServiceError> {
+        let request = self.prove_request(circuit.borrow(), input)?;
+
+        // `headers` replaces the JSON-only `Accept` every request starts with
+        let accept = HeaderMap::from_iter([(
+            ACCEPT,
+            HeaderValue::from_static("text/event-stream, application/json"),
+        )]);
+
+        on_progress(ProofProgress::stage(ProofProgress::STARTED, Some(0.0)));
+        let response = self
+            .send(self.prove_post().headers(accept).json_body(&request)?)
+            .await?;
+        if !response.status().is_success() {
+            return Err(self.service_error(response).await);
+        }
+
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with(EVENT_STREAM));
+
+        let proof = if is_event_stream {
+            let status = response.status().as_u16();
+            let request_id = request_id::from_headers(response.headers());
+            let mut parser = EventParser::default();
+            let mut proof = None;
+            let mut received = 0;
+            let sent = idle::sent_at(&response);
+            let _slot = slot::of(&response);
+            let mut chunks = response.bytes_stream();
+            while proof.is_none() {
+                let Some(chunk) = chunks
+                    .next()
+                    .await
+                    .transpose()
+                    .map_err(|err| self.request_error(err, sent))?
+                else {
+                    break;
+                };
+                received += chunk.len();
+                if let Some(limit) = self.limits.max_response_size
+                    && received > limit
+                {
+                    return Err(BbServiceError::ResponseTooLarge { status, limit });
+                }
+                for event in parser.push(&chunk) {
+                    proof = handle_event(event, &on_progress, self.limits.max_error_len)
+                        .map_err(|err| err.with_request_id(request_id.clone()))?;
+                    if proof.is_some() {
+                        break;
+                    }
+                }
+            }
+            proof.ok_or(BbServiceError::InvalidResponse)?
+        } else {
+            self.json_response::<ProveResponse>(response).await?.proof
+        };
+
+        on_progress(ProofProgress::stage(ProofProgress::FINISHED, Some(100.0)));
+        Ok(proof)
+    }
+}
+
+/// Report a `progress` event, returning the proof once a `proof` event arrives
+fn handle_event(
+    event: Event,
+    on_progress: &impl Fn(ProofProgress),
+    max_error_len: usize,
+) -> Result<Option<ProofData>, BbServiceError> {
+    match event.name.as_str() {
+        "progress" => {
+            let progress =
+                serde_json::from_str(&event.data).map_err(|_| BbServiceError::InvalidResponse)?;
+            on_progress(progress);
+            Ok(None)
+        }
+        "proof" => {
+            let prove_response: ProveResponse =
+                serde_json::from_str(&event.data).map_err(|_| BbServiceError::InvalidResponse)?;
+            Ok(Some(prove_response.proof))
+        }
+        "error" => Err(service_error_from_body(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &event.data,
+            max_error_len,
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// A server-sent event
+#[derive(Debug)]
+struct Event {
+    name: String,
+    data: String,
+}
+
+/// Incremental parser for a server-sent event stream
+///
+/// Raw bytes are buffered and only whole events are decoded, so a chunk
+/// boundary inside a UTF-8 character or a CRLF pair doesn't corrupt the event.
+#[derive(Debug, Default)]
+struct EventParser {
+    buffer: Vec<u8>,
+}
+
+impl EventParser {
+    /// Feed a chunk of the stream, returning the events it completed
+    fn push(&mut self, chunk: &[u8]) -> Vec<Event> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(end) = event_end(&self.buffer) {
+            let block: Vec<u8> = self.buffer.drain(..end).collect();
+            if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+/// Length of the first event block in `buffer`, up to and including the blank
+/// line that ends it, which may use LF or CRLF line endings
+fn event_end(buffer: &[u8]) -> Option<usize> {
+    let mut line_start = 0;
+    for (i, byte) in buffer.iter().enumerate() {
+        if *byte == b'\n' {
+            if matches!(&buffer[line_start..i], b"" | b"\r") && line_start > 0 {
+                return Some(i + 1);
+            }
+            line_start = i + 1;
+        }
+    }
+    None
+}
+
+/// Parse one blank-line terminated event block, skipping comments
+fn parse_event(block: &str) -> Option<Event> {
+    let mut name = "message".to_string();
+    let mut data: Vec<&str> = Vec::new();
+
+    for line in block.lines() {
+        if let Some(value) = line.strip_prefix("event:") {
+            name = value.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+
+    (!data.is_empty()).then(|| Event {
+        name,
+        data: data.join("\n"),
+    })
+}
@@ -0,0 +1,252 @@
+use crate::{BbServiceError, ProofData};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// Size in bytes of a serialized field element
+pub const FIELD_BYTE_SIZE: usize = 32;
+
+/// Number of leading bytes `Debug` shows for each of a proof's byte vectors
+const DEBUG_PREVIEW_LEN: usize = 8;
+
+/// Reject proof data that can't be valid before it's sent for verification
+///
+/// Only empty proof bytes are rejected, empty public inputs are legitimate for
+/// circuits without public inputs or return value.
+pub(crate) fn check_verifiable(proof: &ProofData) -> Result<(), BbServiceError> {
+    if proof.is_empty() {
+        return Err(BbServiceError::InvalidProof(
+            "proof bytes are empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl ProofData {
+    /// Size of the proof bytes, not counting public inputs
+    pub fn proof_size(&self) -> usize {
+        self.proof.len()
+    }
+
+    /// Number of public input field elements
+    ///
+    /// Fails if the public inputs aren't a whole number of field elements.
+    pub fn num_public_inputs(&self) -> Result<usize, BbServiceError> {
+        if !self.public_inputs.len().is_multiple_of(FIELD_BYTE_SIZE) {
+            return Err(BbServiceError::InvalidProof(format!(
+                "public inputs length {} is not divisible by {}",
+                self.public_inputs.len(),
+                FIELD_BYTE_SIZE
+            )));
+        }
+        Ok(self.public_inputs.len() / FIELD_BYTE_SIZE)
+    }
+
+    /// Whether there are no proof bytes
+    ///
+    /// Public inputs aren't considered, they are legitimately empty for
+    /// circuits without public inputs.
+    pub fn is_empty(&self) -> bool {
+        self.proof.is_empty()
+    }
+
+    /// Iterate over the public inputs as [`FIELD_BYTE_SIZE`]-byte field
+    /// elements, big-endian, in the order the verifier expects them
+    ///
+    /// The slices borrow from the proof, nothing is allocated. Fails if the
+    /// public inputs aren't a whole number of field elements, rather than
+    /// silently dropping the trailing bytes.
+    pub fn public_input_chunks(
+        &self,
+    ) -> Result<impl ExactSizeIterator<Item = &[u8]> + DoubleEndedIterator, BbServiceError> {
+        self.num_public_inputs()?;
+        Ok(self.public_inputs.chunks_exact(FIELD_BYTE_SIZE))
+    }
+
+    /// Split the public inputs into field elements rendered as `0x`-prefixed hex
+    /// strings, in the order the verifier expects them
+    ///
+    /// Fails if the public inputs aren't a whole number of field elements.
+    pub fn public_input_fields(&self) -> Result<Vec<String>, BbServiceError> {
+        Ok(self
+            .public_input_chunks()?
+            .map(|chunk| format!("0x{}", hex::encode(chunk)))
+            .collect())
+    }
+
+    /// Lay out the proof for on-chain calldata: the public inputs first, then
+    /// the proof bytes, with nothing in between
+    pub fn to_calldata(&self) -> Vec<u8> {
+        let mut calldata = Vec::with_capacity(self.public_inputs.len() + self.proof.len());
+        calldata.extend_from_slice(&self.public_inputs);
+        calldata.extend_from_slice(&self.proof);
+        calldata
+    }
+
+    /// Split calldata built with [`to_calldata`](Self::to_calldata) back into
+    /// proof data, given the number of public input field elements
+    ///
+    /// Fails if `calldata` is shorter than the public inputs it should contain.
+    pub fn from_calldata(
+        calldata: &[u8],
+        num_public_inputs: usize,
+    ) -> Result<ProofData, BbServiceError> {
+        let public_inputs_len = num_public_inputs
+            .checked_mul(FIELD_BYTE_SIZE)
+            .filter(|len| *len <= calldata.len())
+            .ok_or_else(|| {
+                BbServiceError::InvalidProof(format!(
+                    "calldata of {} bytes is too short for {} public inputs",
+                    calldata.len(),
+                    num_public_inputs
+                ))
+            })?;
+
+        let (public_inputs, proof) = calldata.split_at(public_inputs_len);
+        Ok(ProofData {
+            proof: proof.to_vec(),
+            public_inputs: public_inputs.to_vec(),
+        })
+    }
+
+    /// Hex SHA-256 digest of the proof bytes followed by the public input bytes
+    ///
+    /// Lets a receiver check a proof wasn't corrupted in transit before
+    /// spending time verifying it. Only the two byte strings are hashed, so
+    /// it's an integrity check, not a signature.
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.proof);
+        hasher.update(&self.public_inputs);
+        hex::encode(hasher.finalize())
+    }
+
+    /// Whether [`digest`](Self::digest) matches `expected`, ignoring hex case
+    pub fn verify_digest(&self, expected: &str) -> bool {
+        self.digest().eq_ignore_ascii_case(expected)
+    }
+
+    /// Encode the proof and public inputs as unprefixed hex strings, in that order
+    pub fn to_hex(&self) -> (String, String) {
+        (hex::encode(&self.proof), hex::encode(&self.public_inputs))
+    }
+
+    /// Save the proof data to `path` as JSON, in the same format the service uses
+    ///
+    /// A file that can't be written is reported as [`BbServiceError::Io`].
+    pub fn save_to_file(&self, path: &str) -> Result<(), BbServiceError> {
+        let proof_json = serde_json::to_string(self).map_err(|e| {
+            BbServiceError::InvalidProof(format!("Failed to serialize proof: {}", e))
+        })?;
+
+        fs::write(path, proof_json).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed to write proof file {}: {}", path, e),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Load proof data saved with [`save_to_file`](Self::save_to_file)
+    ///
+    /// A file that can't be read is reported as [`BbServiceError::Io`] and one
+    /// that isn't proof JSON as [`BbServiceError::InvalidProof`].
+    pub fn load_from_file(path: &str) -> Result<ProofData, BbServiceError> {
+        let proof_content = fs::read_to_string(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed to read proof file {}: {}", path, e),
+            )
+        })?;
+
+        serde_json::from_str(&proof_content)
+            .map_err(|e| BbServiceError::InvalidProof(format!("Failed to parse proof JSON: {}", e)))
+    }
+
+    /// Decode proof data from hex strings produced by [`to_hex`](Self::to_hex),
+    /// each optionally prefixed with `0x`
+    pub fn from_hex(proof_hex: &str, public_inputs_hex: &str) -> Result<ProofData, BbServiceError> {
+        Ok(ProofData {
+            proof: decode_hex("proof", proof_hex)?,
+            public_inputs: decode_hex("public inputs", public_inputs_hex)?,
+        })
+    }
+}
+
+/// Proof data with both parts as named hex strings
+///
+/// A self-describing interchange format, e.g. for a JSON API, that can't mix
+/// up the proof and public inputs the way the positional
+/// [`ProofData::from_hex`] arguments can. Serializes as
+/// `{"proof": "...", "publicInputs": "..."}`, with unprefixed hex from
+/// [`from_proof_data`](Self::from_proof_data) and either form accepted back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofDataHex {
+    pub proof: String,
+    #[serde(rename = "publicInputs")]
+    pub public_inputs: String,
+}
+
+impl ProofDataHex {
+    /// Hex-encode `proof_data`
+    pub fn from_proof_data(proof_data: &ProofData) -> Self {
+        let (proof, public_inputs) = proof_data.to_hex();
+        Self {
+            proof,
+            public_inputs,
+        }
+    }
+
+    /// Decode back into [`ProofData`], failing like [`ProofData::from_hex`]
+    pub fn to_proof_data(&self) -> Result<ProofData, BbServiceError> {
+        ProofData::from_hex(&self.proof, &self.public_inputs)
+    }
+}
+
+impl fmt::Display for ProofData {
+    /// Summarize the proof as its sizes, e.g.
+    /// `ProofData { proof: 2176 bytes, public_inputs: 64 bytes }`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ProofData {{ proof: {} bytes, public_inputs: {} bytes }}",
+            self.proof.len(),
+            self.public_inputs.len()
+        )
+    }
+}
+
+impl fmt::Debug for ProofData {
+    /// Show each byte vector's length and first few bytes in hex
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProofData")
+            .field("proof", &BytesPreview(&self.proof))
+            .field("public_inputs", &BytesPreview(&self.public_inputs))
+            .finish()
+    }
+}
+
+/// Debug view of a byte vector as its length and leading bytes
+struct BytesPreview<'a>(&'a [u8]);
+
+impl fmt::Debug for BytesPreview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let preview = &self.0[..self.0.len().min(DEBUG_PREVIEW_LEN)];
+        write!(f, "{} bytes [{}", self.0.len(), hex::encode(preview))?;
+        if self.0.len() > DEBUG_PREVIEW_LEN {
+            f.write_str("..")?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Decode a hex string with an optional `0x` prefix
+fn decode_hex(name: &str, value: &str) -> Result<Vec<u8>, BbServiceError> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(value)
+        .map_err(|e| BbServiceError::InvalidProof(format!("invalid {} hex: {}", name, e)))
+}
@@ -0,0 +1,100 @@
+//! Masking of credentials in `Debug` output
+//!
+//! Clients end up in error reports and logs, so their `Debug` output shows
+//! header names but replaces anything that may hold a secret with
+//! [`REDACTED`].
+
+#[cfg(any(feature = "log", not(target_arch = "wasm32")))]
+use reqwest::Url;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+
+/// Stand-in for a redacted value
+pub(crate) const REDACTED: &str = "***";
+
+/// Header names whose values are always treated as secrets
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Fragments that mark a custom header name as holding a secret
+const SENSITIVE_FRAGMENTS: &[&str] = &["token", "secret", "password", "api-key", "apikey", "auth"];
+
+/// Whether a header's value must not be shown
+///
+/// Values marked with [`HeaderValue::set_sensitive`] are always masked, along
+/// with well-known credential headers and names containing e.g. `token` or
+/// `secret`.
+pub(crate) fn is_sensitive(name: &HeaderName, value: &HeaderValue) -> bool {
+    let name = name.as_str();
+    value.is_sensitive()
+        || SENSITIVE_HEADERS.contains(&name)
+        || SENSITIVE_FRAGMENTS
+            .iter()
+            .any(|fragment| name.contains(fragment))
+}
+
+/// Shows a header map with sensitive values masked
+pub(crate) struct Headers<'a>(pub(crate) &'a HeaderMap);
+
+impl fmt::Debug for Headers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.0
+                    .iter()
+                    .map(|(name, value)| (name, Header(name, value))),
+            )
+            .finish()
+    }
+}
+
+/// Shows the client's credentials header by name only
+pub(crate) struct Auth<'a>(pub(crate) &'a Option<(HeaderName, HeaderValue)>);
+
+impl fmt::Debug for Auth<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some((name, _)) => f.debug_tuple("Some").field(&(name, Secret)).finish(),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+/// A header value, masked if sensitive
+struct Header<'a>(&'a HeaderName, &'a HeaderValue);
+
+impl fmt::Debug for Header<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_sensitive(self.0, self.1) {
+            Secret.fmt(f)
+        } else {
+            self.1.fmt(f)
+        }
+    }
+}
+
+/// A value that is never shown
+pub(crate) struct Secret;
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(REDACTED, f)
+    }
+}
+
+/// A URL without its userinfo, query string or fragment, which may carry
+/// credentials
+#[cfg(any(feature = "log", not(target_arch = "wasm32")))]
+pub(crate) fn url(url: &Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.set_query(None);
+    url.set_fragment(None);
+    url.to_string()
+}
@@ -0,0 +1,51 @@
+//! Request IDs for correlating client calls with service logs
+
+use crate::body::JsonBody;
+use crate::{BbServiceClient, BbServiceError, CompiledCircuit, InputMap, ProofData, ProveResponse};
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::borrow::Borrow;
+
+/// Header carrying a request's correlation ID, sent by
+/// [`BbServiceClient::generate_proof_with_request_id`] and read back from
+/// error responses into [`BbServiceError::request_id`]
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+impl BbServiceClient {
+    /// Generate a proof, sending `request_id` as the request's `X-Request-Id`
+    ///
+    /// Pass the ID your gateway assigned to the surrounding operation so the
+    /// service's logs can be correlated with your own. Retries reuse the same
+    /// ID, and it replaces any `X-Request-Id` set with
+    /// [`BbServiceClientBuilder::header`](crate::BbServiceClientBuilder::header).
+    /// Fails with [`BbServiceError::InvalidConfig`] if `request_id` isn't
+    /// a valid header value.
+    pub async fn generate_proof_with_request_id(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+        request_id: &str,
+    ) -> Result<ProofData, BbServiceError> {
+        let request_id = HeaderValue::from_str(request_id).map_err(|_| {
+            BbServiceError::InvalidConfig(format!("invalid request id: {}", request_id))
+        })?;
+        let request = self.prove_request(circuit.borrow(), input)?;
+        // `headers` replaces rather than appends, so an ID set on the client
+        // isn't sent alongside this one
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, request_id);
+
+        let response = self
+            .send(self.prove_post().headers(headers).json_body(&request)?)
+            .await?;
+        let prove_response: ProveResponse = self.json_response(response).await?;
+        Ok(prove_response.proof)
+    }
+}
+
+/// The request ID a response was tagged with, if any
+pub(crate) fn from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
@@ -0,0 +1,100 @@
+use crate::platform;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Error as ReqwestError, Response, StatusCode};
+use std::time::Duration;
+
+/// Default delay before the first retry
+const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Default upper bound on any single backoff delay
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Default longest `Retry-After` waited for
+const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Controls how failed requests are retried
+///
+/// Each retry waits `base_delay * 2^attempt` with jitter, capped at `max_delay`,
+/// so the time spent waiting is bounded by `max_retries * max_delay`. A
+/// `Retry-After` from the service replaces the backoff delay and isn't capped
+/// by `max_delay`, but one longer than `max_retry_after` stops retrying rather
+/// than being waited out. Retrying stops
+/// once `max_retries` is used up or the next wait would end past `deadline`,
+/// whichever comes first. Without a `deadline` the total time also includes
+/// the attempts themselves, bounded only by the client's timeout.
+#[derive(Debug, Clone)]
+pub(crate) struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) max_delay: Duration,
+    pub(crate) max_retry_after: Duration,
+    pub(crate) deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+            deadline: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (starting at 0)
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        // Equal jitter: wait at least half the delay, plus a random share of the rest
+        let half = exponential / 2;
+        half + half.mul_f64(fastrand::f64())
+    }
+
+    /// Whether the service's `Retry-After`, if any, is short enough to wait for
+    pub(crate) fn accepts_retry_after(&self, retry_after: Option<Duration>) -> bool {
+        retry_after.is_none_or(|delay| delay <= self.max_retry_after)
+    }
+
+    /// Whether waiting `delay` before the next attempt still ends within the
+    /// deadline, `elapsed` after the first attempt started
+    pub(crate) fn within_deadline(&self, elapsed: Duration, delay: Duration) -> bool {
+        self.deadline
+            .is_none_or(|deadline| elapsed.saturating_add(delay) <= deadline)
+    }
+
+    /// Whether the outcome of an attempt should be retried
+    pub(crate) fn should_retry(result: &Result<Response, ReqwestError>) -> bool {
+        match result {
+            Ok(response) => Self::should_retry_status(response.status()),
+            Err(err) => Self::should_retry_error(err),
+        }
+    }
+
+    /// Whether a response status should be retried
+    pub(crate) fn should_retry_status(status: StatusCode) -> bool {
+        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// Whether a transport error should be retried
+    pub(crate) fn should_retry_error(err: &ReqwestError) -> bool {
+        !err.is_timeout() && (platform::is_connect(err) || err.is_request())
+    }
+}
+
+/// Parse the `Retry-After` header, in either delta-seconds or HTTP-date form
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    // A date in the past means the service is ready now
+    Some(platform::until(date))
+}
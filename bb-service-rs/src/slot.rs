@@ -0,0 +1,36 @@
+//! Slots of the client's concurrency limit, held until a response's body has
+//! been read
+//!
+//! A request takes its slot before it is sent, and the slot travels with the
+//! response in its extensions. Readers take a handle on it before consuming
+//! the body, so a large proof download still counts against the limit. In the
+//! browser, where responses have no extensions, the slot is given up once the
+//! headers arrive.
+
+use reqwest::Response;
+use std::sync::Arc;
+use tokio::sync::OwnedSemaphorePermit;
+
+/// A handle on the slot a response holds, released once every handle is
+/// dropped
+#[derive(Debug, Clone)]
+pub(crate) struct Slot(#[allow(dead_code)] Arc<OwnedSemaphorePermit>);
+
+/// Keep `permit` on `response` until its body is read
+pub(crate) fn hold(response: &mut Response, permit: OwnedSemaphorePermit) {
+    #[cfg(not(target_arch = "wasm32"))]
+    response.extensions_mut().insert(Slot(Arc::new(permit)));
+    #[cfg(target_arch = "wasm32")]
+    let _ = (response, permit);
+}
+
+/// The slot `response` holds, to keep while reading its body
+pub(crate) fn of(response: &Response) -> Option<Slot> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return response.extensions().get::<Slot>().cloned();
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = response;
+        None
+    }
+}
@@ -0,0 +1,268 @@
+use crate::body::JsonBody;
+use crate::{BbServiceClient, BbServiceError, CompiledCircuit, InputMap, idle, slot};
+use futures::StreamExt;
+use serde::de::IgnoredAny;
+use std::borrow::Borrow;
+use std::io::Write;
+
+/// What [`BbServiceClient::generate_proof_to_writer`] learned about a proof
+/// written to a writer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofMeta {
+    /// Number of proof bytes written
+    pub proof_len: usize,
+    /// The proof's public inputs, which are small enough to keep in memory
+    pub public_inputs: Vec<u8>,
+}
+
+impl ProofMeta {
+    /// Length of the public inputs in bytes
+    pub fn public_inputs_len(&self) -> usize {
+        self.public_inputs.len()
+    }
+}
+
+impl BbServiceClient {
+    /// Generate a proof and write the proof bytes to `writer` as they arrive
+    ///
+    /// The response is parsed incrementally, so the proof is never held in
+    /// memory as a whole and is not part of the returned [`ProofMeta`]. A
+    /// response whose `Content-Type` isn't JSON fails with
+    /// [`BbServiceError::MalformedResponse`] before anything is written, but
+    /// after any other error part of the proof may already have been written.
+    pub async fn generate_proof_to_writer<W: Write>(
+        &self,
+        circuit: impl Borrow<CompiledCircuit>,
+        input: InputMap,
+        mut writer: W,
+    ) -> Result<ProofMeta, BbServiceError> {
+        let request = self.prove_request(circuit.borrow(), input)?;
+
+        let response = self.send(self.prove_post().json_body(&request)?).await?;
+        if !response.status().is_success() {
+            return Err(self.service_error(response).await);
+        }
+        // A body that isn't JSON, such as a proxy's HTML page, is rejected
+        // like any other success body, before anything is written
+        let content_type = crate::content_type(response.headers());
+        if content_type
+            .as_deref()
+            .is_some_and(|value| !crate::is_json(value))
+        {
+            let status = response.status();
+            let body = self.read_body(response).await?;
+            return crate::parse_success_body::<IgnoredAny>(status, content_type.as_deref(), &body)
+                .and(Err(BbServiceError::InvalidResponse));
+        }
+
+        let mut parser = ProveResponseParser::default();
+        let mut proof = Vec::new();
+        let sent = idle::sent_at(&response);
+        let _slot = slot::of(&response);
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks
+            .next()
+            .await
+            .transpose()
+            .map_err(|err| self.request_error(err, sent))?
+        {
+            parser.push(&chunk, &mut proof)?;
+            writer.write_all(&proof)?;
+            proof.clear();
+        }
+        writer.flush()?;
+
+        parser.finish()
+    }
+}
+
+/// Where a JSON container sits in the response
+#[derive(Debug)]
+enum Frame {
+    Object {
+        key: Option<Vec<u8>>,
+        awaiting_key: bool,
+    },
+    Array,
+}
+
+/// A partially read token, which may span chunks
+#[derive(Debug, Default)]
+enum Token {
+    #[default]
+    None,
+    String {
+        bytes: Vec<u8>,
+        escaped: bool,
+    },
+    Scalar(Vec<u8>),
+}
+
+/// Incremental parser for a `/prove` response that hands out the bytes of
+/// `proof.proof` as soon as they're read and keeps `proof.publicInputs`
+#[derive(Debug, Default)]
+struct ProveResponseParser {
+    stack: Vec<Frame>,
+    token: Token,
+    proof_len: usize,
+    public_inputs: Vec<u8>,
+    seen_proof: bool,
+    done: bool,
+}
+
+impl ProveResponseParser {
+    /// Parse the next chunk of the body, appending proof bytes to `proof`
+    fn push(&mut self, chunk: &[u8], proof: &mut Vec<u8>) -> Result<(), BbServiceError> {
+        for &byte in chunk {
+            self.push_byte(byte, proof)?;
+        }
+        Ok(())
+    }
+
+    /// Parse one byte of the body
+    fn push_byte(&mut self, byte: u8, proof: &mut Vec<u8>) -> Result<(), BbServiceError> {
+        match &mut self.token {
+            Token::String { bytes, escaped } => {
+                if *escaped {
+                    *escaped = false;
+                    bytes.push(byte);
+                } else if byte == b'\\' {
+                    *escaped = true;
+                } else if byte == b'"' {
+                    let bytes = std::mem::take(bytes);
+                    self.token = Token::None;
+                    self.string(bytes);
+                } else {
+                    bytes.push(byte);
+                }
+                return Ok(());
+            }
+            Token::Scalar(bytes) => {
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'+' | b'.') {
+                    bytes.push(byte);
+                    return Ok(());
+                }
+                let bytes = std::mem::take(bytes);
+                self.token = Token::None;
+                self.scalar(&bytes, proof)?;
+            }
+            Token::None => {}
+        }
+
+        if self.done && !byte.is_ascii_whitespace() {
+            return Err(BbServiceError::InvalidResponse);
+        }
+        match byte {
+            b'{' => self.stack.push(Frame::Object {
+                key: None,
+                awaiting_key: true,
+            }),
+            b'[' => self.stack.push(Frame::Array),
+            b'}' | b']' => {
+                if byte == b']' && matches!(self.byte_array(), Some(ByteArray::Proof)) {
+                    self.seen_proof = true;
+                }
+                match (byte, self.stack.pop()) {
+                    (b'}', Some(Frame::Object { .. })) | (b']', Some(Frame::Array)) => {}
+                    _ => return Err(BbServiceError::InvalidResponse),
+                }
+                self.value_done();
+            }
+            b'"' => {
+                self.token = Token::String {
+                    bytes: Vec::new(),
+                    escaped: false,
+                }
+            }
+            b',' => {
+                if let Some(Frame::Object { awaiting_key, .. }) = self.stack.last_mut() {
+                    *awaiting_key = true;
+                }
+            }
+            b':' => {}
+            byte if byte.is_ascii_whitespace() => {}
+            byte => self.token = Token::Scalar(vec![byte]),
+        }
+        Ok(())
+    }
+
+    /// Handle a complete string, either an object key or a value
+    fn string(&mut self, bytes: Vec<u8>) {
+        if let Some(Frame::Object { key, awaiting_key }) = self.stack.last_mut()
+            && *awaiting_key
+        {
+            *key = Some(bytes);
+            *awaiting_key = false;
+            return;
+        }
+        self.value_done();
+    }
+
+    /// Handle a complete number or literal, keeping it if it's a proof byte
+    fn scalar(&mut self, bytes: &[u8], proof: &mut Vec<u8>) -> Result<(), BbServiceError> {
+        let target = self.byte_array();
+        if target.is_some() {
+            let value = std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|value| value.parse::<u8>().ok())
+                .ok_or(BbServiceError::InvalidResponse)?;
+            match target {
+                Some(ByteArray::Proof) => {
+                    proof.push(value);
+                    self.proof_len += 1;
+                }
+                Some(ByteArray::PublicInputs) => self.public_inputs.push(value),
+                None => {}
+            }
+        }
+        self.value_done();
+        Ok(())
+    }
+
+    /// Mark the value at the top of the stack as complete
+    fn value_done(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Object { awaiting_key, .. }) => *awaiting_key = true,
+            Some(Frame::Array) => {}
+            None => self.done = true,
+        }
+    }
+
+    /// Which of the proof's byte arrays the parser is inside, if any
+    fn byte_array(&self) -> Option<ByteArray> {
+        match self.stack.as_slice() {
+            [
+                Frame::Object {
+                    key: Some(outer), ..
+                },
+                Frame::Object {
+                    key: Some(inner), ..
+                },
+                Frame::Array,
+            ] if outer == b"proof" => match inner.as_slice() {
+                b"proof" => Some(ByteArray::Proof),
+                b"publicInputs" => Some(ByteArray::PublicInputs),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Check the whole response was read and contained a proof
+    fn finish(self) -> Result<ProofMeta, BbServiceError> {
+        if !self.done || !self.seen_proof {
+            return Err(BbServiceError::InvalidResponse);
+        }
+        Ok(ProofMeta {
+            proof_len: self.proof_len,
+            public_inputs: self.public_inputs,
+        })
+    }
+}
+
+/// The byte arrays of a proof
+#[derive(Debug, Clone, Copy)]
+enum ByteArray {
+    Proof,
+    PublicInputs,
+}
@@ -0,0 +1,14 @@
+use crate::platform::Instant;
+use reqwest::{Error as ReqwestError, Response};
+use tracing::Span;
+
+/// Record the final response status and elapsed time on the current span
+///
+/// Only spans that declare `status` and `elapsed_ms` fields pick them up.
+pub(crate) fn record_response(result: &Result<Response, ReqwestError>, started: Instant) {
+    let span = Span::current();
+    if let Ok(response) = result {
+        span.record("status", response.status().as_u16());
+    }
+    span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+}
@@ -0,0 +1,126 @@
+use crate::{BbServiceError, redact};
+use reqwest::{Proxy, Url};
+use std::fmt;
+use std::time::Duration;
+
+/// Settings for the underlying reqwest client, applied only when the builder
+/// creates that client itself
+#[derive(Default)]
+pub(crate) struct TransportConfig {
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+    pub(crate) pool_idle_timeout: Option<Duration>,
+    pub(crate) http2_prior_knowledge: bool,
+    pub(crate) http_proxy: Option<String>,
+    pub(crate) https_proxy: Option<String>,
+    pub(crate) proxy_auth: Option<(String, String)>,
+    #[cfg(feature = "compression")]
+    pub(crate) decompress_responses: Option<bool>,
+}
+
+impl fmt::Debug for TransportConfig {
+    /// Shows proxies without their userinfo and the proxy password masked
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut config = f.debug_struct("TransportConfig");
+        config
+            .field("connect_timeout", &self.connect_timeout)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("http2_prior_knowledge", &self.http2_prior_knowledge)
+            .field("http_proxy", &self.http_proxy.as_deref().map(redact_proxy))
+            .field(
+                "https_proxy",
+                &self.https_proxy.as_deref().map(redact_proxy),
+            )
+            .field(
+                "proxy_auth",
+                &self
+                    .proxy_auth
+                    .as_ref()
+                    .map(|(username, _)| (username, redact::Secret)),
+            );
+        #[cfg(feature = "compression")]
+        config.field("decompress_responses", &self.decompress_responses);
+        config.finish()
+    }
+}
+
+/// A proxy URL as shown by `Debug`, masked entirely if it doesn't parse
+fn redact_proxy(proxy: &str) -> String {
+    Url::parse(proxy).map_or_else(|_| redact::REDACTED.to_string(), |url| redact::url(&url))
+}
+
+/// Apply a [`TransportConfig`] to either reqwest client builder, which share
+/// method names but no trait
+macro_rules! configure {
+    ($config:expr, $builder:expr) => {{
+        let config = $config;
+        let mut builder = $builder;
+        if let Some(connect_timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(max) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = config.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if config.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        for proxy in config.proxies()? {
+            builder = builder.proxy(proxy);
+        }
+        #[cfg(feature = "compression")]
+        if let Some(enabled) = config.decompress_responses {
+            builder = builder.gzip(enabled).brotli(enabled);
+        }
+        builder
+    }};
+}
+
+impl TransportConfig {
+    /// Configure an async client builder
+    pub(crate) fn apply(
+        &self,
+        builder: reqwest::ClientBuilder,
+    ) -> Result<reqwest::ClientBuilder, BbServiceError> {
+        Ok(configure!(self, builder))
+    }
+
+    /// Configure a blocking client builder
+    #[cfg(feature = "blocking")]
+    pub(crate) fn apply_blocking(
+        &self,
+        builder: reqwest::blocking::ClientBuilder,
+    ) -> Result<reqwest::blocking::ClientBuilder, BbServiceError> {
+        Ok(configure!(self, builder))
+    }
+
+    /// The configured proxies, with basic auth attached if set
+    fn proxies(&self) -> Result<Vec<Proxy>, BbServiceError> {
+        let mut proxies = Vec::new();
+        if let Some(url) = &self.http_proxy {
+            proxies.push(self.proxy(url, Proxy::http(url))?);
+        }
+        if let Some(url) = &self.https_proxy {
+            proxies.push(self.proxy(url, Proxy::https(url))?);
+        }
+        Ok(proxies)
+    }
+
+    /// Attach basic auth to a parsed proxy, naming `url` if it failed to parse
+    fn proxy(
+        &self,
+        url: &str,
+        proxy: Result<Proxy, reqwest::Error>,
+    ) -> Result<Proxy, BbServiceError> {
+        let proxy = proxy.map_err(|e| {
+            BbServiceError::InvalidConfig(format!("invalid proxy URL {}: {}", url, e))
+        })?;
+        Ok(match &self.proxy_auth {
+            Some((username, password)) => proxy.basic_auth(username, password),
+            None => proxy,
+        })
+    }
+}
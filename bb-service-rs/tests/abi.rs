@@ -0,0 +1,273 @@
+mod common;
+
+use bb_service_rs::{
+    AbiField, AbiType, AbiVisibility, BbServiceError, FIELD_BYTE_SIZE, ProofData, Sign,
+    circuit_return_type, load_circuit_definition_blocking, named_public_inputs, parse_artifact,
+};
+use common::fixture_path;
+use serde_json::json;
+
+fn u8_type() -> AbiType {
+    AbiType::Integer {
+        sign: Sign::Unsigned,
+        width: 8,
+    }
+}
+
+#[test]
+fn parse_artifact_reads_fixture_abi() {
+    let circuit = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    let artifact = parse_artifact(&circuit).unwrap();
+
+    assert_eq!(artifact.bytecode, circuit["bytecode"].as_str().unwrap());
+
+    let names: Vec<&str> = artifact
+        .abi
+        .parameters
+        .iter()
+        .map(|parameter| parameter.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "input_and_blinder",
+            "needle",
+            "input_length",
+            "needle_length"
+        ]
+    );
+
+    let needle = &artifact.abi.parameters[1];
+    assert_eq!(needle.visibility, AbiVisibility::Public);
+    assert_eq!(
+        needle.param_type,
+        AbiType::Array {
+            length: 128,
+            elem: Box::new(u8_type()),
+        }
+    );
+
+    let return_type = artifact.abi.return_type.unwrap();
+    assert_eq!(return_type.visibility, AbiVisibility::Public);
+    assert_eq!(
+        return_type.abi_type,
+        AbiType::Tuple {
+            fields: vec![
+                AbiType::Array {
+                    length: 32,
+                    elem: Box::new(u8_type()),
+                },
+                AbiType::Boolean,
+            ],
+        }
+    );
+}
+
+#[test]
+fn parse_artifact_handles_structs_and_missing_return_type() {
+    let circuit = json!({
+        "bytecode": "H4sIAAAAAAAA/w==",
+        "abi": {
+            "parameters": [{
+                "name": "point",
+                "type": {
+                    "kind": "struct",
+                    "path": "Point",
+                    "fields": [
+                        { "name": "x", "type": { "kind": "field" } },
+                        { "name": "label", "type": { "kind": "string", "length": 4 } }
+                    ]
+                },
+                "visibility": "private"
+            }],
+            "return_type": null,
+            "error_types": {}
+        }
+    });
+
+    let artifact = parse_artifact(&circuit).unwrap();
+    assert!(artifact.abi.return_type.is_none());
+    assert_eq!(
+        artifact.abi.parameters[0].param_type,
+        AbiType::Struct {
+            path: "Point".to_string(),
+            fields: vec![
+                AbiField {
+                    name: "x".to_string(),
+                    field_type: AbiType::Field,
+                },
+                AbiField {
+                    name: "label".to_string(),
+                    field_type: AbiType::String { length: 4 },
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn parse_artifact_rejects_malformed_abi() {
+    let circuit = json!({ "bytecode": "", "abi": { "parameters": [{ "name": "x" }] } });
+    assert!(matches!(
+        parse_artifact(&circuit),
+        Err(BbServiceError::Circuit(_))
+    ));
+}
+
+/// An ABI-only circuit returning a value of type `return_type`
+fn returning(return_type: serde_json::Value) -> serde_json::Value {
+    json!({
+        "abi": {
+            "parameters": [],
+            "return_type": { "abi_type": return_type, "visibility": "public" }
+        }
+    })
+}
+
+#[test]
+fn circuit_return_type_reads_common_shapes() {
+    let circuit = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    assert!(matches!(
+        circuit_return_type(&circuit).unwrap(),
+        Some(AbiType::Tuple { fields }) if fields.len() == 2
+    ));
+
+    let cases = [
+        (json!({ "kind": "field" }), AbiType::Field),
+        (json!({ "kind": "boolean" }), AbiType::Boolean),
+        (
+            json!({ "kind": "integer", "sign": "signed", "width": 64 }),
+            AbiType::Integer {
+                sign: Sign::Signed,
+                width: 64,
+            },
+        ),
+        (
+            json!({ "kind": "array", "length": 3, "type": { "kind": "field" } }),
+            AbiType::Array {
+                length: 3,
+                elem: Box::new(AbiType::Field),
+            },
+        ),
+        (
+            json!({
+                "kind": "struct",
+                "path": "main::Output",
+                "fields": [
+                    { "name": "ok", "type": { "kind": "boolean" } },
+                    {
+                        "name": "digest",
+                        "type": { "kind": "array", "length": 32, "type": { "kind": "integer", "sign": "unsigned", "width": 8 } }
+                    }
+                ]
+            }),
+            AbiType::Struct {
+                path: "main::Output".to_string(),
+                fields: vec![
+                    AbiField {
+                        name: "ok".to_string(),
+                        field_type: AbiType::Boolean,
+                    },
+                    AbiField {
+                        name: "digest".to_string(),
+                        field_type: AbiType::Array {
+                            length: 32,
+                            elem: Box::new(u8_type()),
+                        },
+                    },
+                ],
+            },
+        ),
+    ];
+    for (return_type, expected) in cases {
+        assert_eq!(
+            circuit_return_type(&returning(return_type)).unwrap(),
+            Some(expected)
+        );
+    }
+}
+
+#[test]
+fn circuit_return_type_handles_circuits_without_one() {
+    let circuit = json!({ "abi": { "parameters": [], "return_type": null } });
+    assert_eq!(circuit_return_type(&circuit).unwrap(), None);
+    let circuit = json!({ "abi": { "parameters": [] } });
+    assert_eq!(circuit_return_type(&circuit).unwrap(), None);
+
+    assert!(matches!(
+        circuit_return_type(&json!({ "bytecode": "" })),
+        Err(BbServiceError::Circuit(_))
+    ));
+    assert!(matches!(
+        circuit_return_type(&returning(json!({ "kind": "map" }))),
+        Err(BbServiceError::Circuit(_))
+    ));
+}
+
+/// Public inputs whose `i`th field element is `i`
+fn numbered_public_inputs(count: usize) -> ProofData {
+    let mut public_inputs = vec![0; count * FIELD_BYTE_SIZE];
+    for index in 0..count {
+        public_inputs[(index + 1) * FIELD_BYTE_SIZE - 1] = index as u8;
+    }
+    ProofData {
+        proof: vec![],
+        public_inputs,
+    }
+}
+
+fn field_hex(value: u8) -> String {
+    format!("0x{}{:02x}", "00".repeat(FIELD_BYTE_SIZE - 1), value)
+}
+
+#[test]
+fn named_public_inputs_follow_fixture_abi() {
+    let circuit = load_circuit_definition_blocking(&fixture_path()).unwrap();
+
+    // needle [u8; 128], input_length, needle_length, return ([u8; 32], bool)
+    let named = named_public_inputs(&circuit, &numbered_public_inputs(163)).unwrap();
+
+    assert_eq!(named.len(), 163);
+    assert_eq!(named["needle[0]"], field_hex(0));
+    assert_eq!(named["needle[127]"], field_hex(127));
+    assert_eq!(named["input_length"], field_hex(128));
+    assert_eq!(named["needle_length"], field_hex(129));
+    assert_eq!(named["return.0[0]"], field_hex(130));
+    assert_eq!(named["return.1"], field_hex(162));
+    assert!(!named.contains_key("input_and_blinder[0]"));
+}
+
+#[test]
+fn named_public_inputs_flatten_structs() {
+    let circuit = json!({
+        "bytecode": "H4sIAAAAAAAA/w==",
+        "abi": {
+            "parameters": [
+                {
+                    "name": "point",
+                    "type": {
+                        "kind": "struct",
+                        "path": "Point",
+                        "fields": [
+                            { "name": "x", "type": { "kind": "field" } },
+                            { "name": "y", "type": { "kind": "field" } }
+                        ]
+                    },
+                    "visibility": "public"
+                },
+                { "name": "secret", "type": { "kind": "field" }, "visibility": "private" }
+            ],
+            "return_type": null
+        }
+    });
+
+    let named = named_public_inputs(&circuit, &numbered_public_inputs(2)).unwrap();
+    assert_eq!(named.len(), 2);
+    assert_eq!(named["point.x"], field_hex(0));
+    assert_eq!(named["point.y"], field_hex(1));
+
+    assert!(matches!(
+        named_public_inputs(&circuit, &numbered_public_inputs(3)),
+        Err(BbServiceError::InvalidProof(_))
+    ));
+}
@@ -0,0 +1,492 @@
+mod common;
+
+use bb_service_rs::{BbServiceClient, BbServiceError, InputMap, ProofData};
+use common::test_circuit;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{body_partial_json, headers, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn input(x: u64) -> InputMap {
+    InputMap::from([("x".to_string(), json!(x))])
+}
+
+fn proof_body(byte: u8) -> serde_json::Value {
+    json!({
+        "message": "Proof generated successfully",
+        "proof": { "proof": [byte], "publicInputs": [] }
+    })
+}
+
+async fn mount_single_prove(server: &MockServer, x: u64, response: ResponseTemplate) {
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .and(body_partial_json(json!({ "input": { "x": x } })))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn batch_endpoint_returns_proofs_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove/batch"))
+        .and(body_partial_json(
+            json!({ "inputs": [{ "x": 1 }, { "x": 2 }] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proofs generated successfully",
+            "proofs": [
+                { "proof": [1], "publicInputs": [] },
+                { "proof": [2], "publicInputs": [] }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let proofs = client
+        .generate_proofs_batch(test_circuit(), vec![input(1), input(2)])
+        .await
+        .unwrap();
+
+    assert_eq!(proofs[0].proof, vec![1]);
+    assert_eq!(proofs[1].proof, vec![2]);
+}
+
+#[tokio::test]
+async fn batch_falls_back_to_single_proofs() {
+    let server = MockServer::start().await;
+    for x in 1..=5 {
+        mount_single_prove(
+            &server,
+            x,
+            ResponseTemplate::new(200).set_body_json(proof_body(x as u8)),
+        )
+        .await;
+    }
+
+    let client = BbServiceClient::new(server.uri());
+    let inputs = (1..=5).map(input).collect();
+    let proofs = client
+        .generate_proofs_batch(test_circuit(), inputs)
+        .await
+        .unwrap();
+
+    let bytes: Vec<u8> = proofs.iter().map(|proof| proof.proof[0]).collect();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn batch_fallback_reports_failing_index() {
+    let server = MockServer::start().await;
+    mount_single_prove(
+        &server,
+        1,
+        ResponseTemplate::new(200).set_body_json(proof_body(1)),
+    )
+    .await;
+    mount_single_prove(
+        &server,
+        2,
+        ResponseTemplate::new(500).set_body_json(json!({ "error": "Failed to generate proof" })),
+    )
+    .await;
+
+    let client = BbServiceClient::new(server.uri());
+    match client
+        .generate_proofs_batch(test_circuit(), vec![input(1), input(2)])
+        .await
+    {
+        Err(BbServiceError::Batch { index, source }) => {
+            assert_eq!(index, 1);
+            assert!(matches!(
+                *source,
+                BbServiceError::Service { status: 500, .. }
+            ));
+        }
+        other => panic!("expected a batch error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn concurrent_proving_respects_limit() {
+    let server = MockServer::start().await;
+    for x in 1..=6 {
+        mount_single_prove(
+            &server,
+            x,
+            ResponseTemplate::new(200)
+                .set_body_json(proof_body(x as u8))
+                .set_delay(Duration::from_millis(100)),
+        )
+        .await;
+    }
+
+    let client = BbServiceClient::new(server.uri());
+    let inputs = (1..=6).map(input).collect();
+    let started = Instant::now();
+    let proofs = client
+        .generate_proofs_concurrent(test_circuit(), inputs, 2)
+        .await
+        .unwrap();
+
+    // Six 100ms requests two at a time take at least three rounds
+    assert!(started.elapsed() >= Duration::from_millis(300));
+    let bytes: Vec<u8> = proofs.iter().map(|proof| proof.proof[0]).collect();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[tokio::test]
+async fn concurrent_proving_reports_failing_index() {
+    let server = MockServer::start().await;
+    for x in [1, 2, 4] {
+        mount_single_prove(
+            &server,
+            x,
+            ResponseTemplate::new(200).set_body_json(proof_body(x as u8)),
+        )
+        .await;
+    }
+    mount_single_prove(
+        &server,
+        3,
+        ResponseTemplate::new(400).set_body_json(json!({ "error": "Invalid input" })),
+    )
+    .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let inputs = (1..=4).map(input).collect();
+    match client
+        .generate_proofs_concurrent(test_circuit(), inputs, 4)
+        .await
+    {
+        Err(BbServiceError::Batch { index, .. }) => assert_eq!(index, 2),
+        other => panic!("expected a batch error, got {:?}", other),
+    }
+}
+
+fn proof(byte: u8) -> ProofData {
+    ProofData {
+        proof: vec![byte],
+        public_inputs: vec![],
+    }
+}
+
+async fn mount_vk(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/vk"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Verification key generated successfully",
+            "vk": [9, 9]
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+async fn mount_verify_with_vk(server: &MockServer, byte: u8, response: ResponseTemplate) {
+    Mock::given(method("POST"))
+        .and(path("/verify/vk"))
+        .and(body_partial_json(
+            json!({ "vk": [9, 9], "proof": { "proof": [byte] } }),
+        ))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+fn verify_body(is_valid: bool) -> serde_json::Value {
+    json!({ "message": "Proof verification completed", "isValid": is_valid })
+}
+
+#[tokio::test]
+async fn verify_batch_endpoint_returns_results_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/verify/batch"))
+        .and(body_partial_json(json!({
+            "proofs": [{ "proof": [1] }, { "proof": [2] }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proofs verified",
+            "results": [true, false]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let results = client
+        .verify_proofs_batch(test_circuit(), vec![proof(1), proof(2)])
+        .await
+        .unwrap();
+
+    assert_eq!(results, vec![true, false]);
+}
+
+#[tokio::test]
+async fn verify_batch_falls_back_to_a_shared_vk() {
+    let server = MockServer::start().await;
+    mount_vk(&server).await;
+    for byte in 1..=5 {
+        mount_verify_with_vk(
+            &server,
+            byte,
+            ResponseTemplate::new(200).set_body_json(verify_body(byte % 2 == 1)),
+        )
+        .await;
+    }
+
+    let client = BbServiceClient::new(server.uri());
+    let proofs = (1..=5).map(proof).collect();
+    let results = client
+        .verify_proofs_batch(test_circuit(), proofs)
+        .await
+        .unwrap();
+
+    assert_eq!(results, vec![true, false, true, false, true]);
+}
+
+#[tokio::test]
+async fn verify_batch_fallback_reports_failing_index() {
+    let server = MockServer::start().await;
+    mount_vk(&server).await;
+    mount_verify_with_vk(
+        &server,
+        1,
+        ResponseTemplate::new(200).set_body_json(verify_body(false)),
+    )
+    .await;
+    mount_verify_with_vk(
+        &server,
+        2,
+        ResponseTemplate::new(500).set_body_json(json!({ "error": "Failed to verify proof" })),
+    )
+    .await;
+
+    let client = BbServiceClient::new(server.uri());
+    match client
+        .verify_proofs_batch(test_circuit(), vec![proof(1), proof(2)])
+        .await
+    {
+        Err(BbServiceError::Batch { index, source }) => {
+            assert_eq!(index, 1);
+            assert!(matches!(
+                *source,
+                BbServiceError::Service { status: 500, .. }
+            ));
+        }
+        other => panic!("expected a batch error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn verify_batch_rejects_empty_proofs_before_sending() {
+    let server = MockServer::start().await;
+    let client = BbServiceClient::new(server.uri());
+
+    match client
+        .verify_proofs_batch(
+            test_circuit(),
+            vec![
+                proof(1),
+                ProofData {
+                    proof: vec![],
+                    public_inputs: vec![],
+                },
+            ],
+        )
+        .await
+    {
+        Err(BbServiceError::Batch { index, source }) => {
+            assert_eq!(index, 1);
+            assert!(matches!(*source, BbServiceError::InvalidProof(_)));
+        }
+        other => panic!("expected a batch error, got {:?}", other),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+/// A `/prove/batch` response streaming `lines` as newline-delimited JSON
+fn ndjson(lines: &[serde_json::Value]) -> ResponseTemplate {
+    let body: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    ResponseTemplate::new(200).set_body_raw(body, "application/x-ndjson")
+}
+
+async fn collect_stream(
+    client: &BbServiceClient,
+    inputs: Vec<InputMap>,
+) -> Vec<Result<ProofData, BbServiceError>> {
+    client
+        .generate_proofs_stream(test_circuit(), inputs)
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn proof_stream_yields_ndjson_lines_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove/batch"))
+        .and(headers(
+            "accept",
+            vec!["application/x-ndjson", "application/json"],
+        ))
+        .respond_with(ndjson(&[
+            json!({ "proof": [1], "publicInputs": [] }),
+            json!({ "proof": [2], "publicInputs": [] }),
+            json!({ "proof": [3], "publicInputs": [] }),
+        ]))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let proofs = collect_stream(&client, (1..=3).map(input).collect()).await;
+
+    let bytes: Vec<u8> = proofs
+        .into_iter()
+        .map(|proof| proof.unwrap().proof[0])
+        .collect();
+    assert_eq!(bytes, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn proof_stream_ends_with_a_mid_stream_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove/batch"))
+        .respond_with(ndjson(&[
+            json!({ "proof": [1], "publicInputs": [] }),
+            json!({ "error": "Witness generation failed", "details": "x out of range" }),
+            json!({ "proof": [3], "publicInputs": [] }),
+        ]))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let results = collect_stream(&client, (1..=3).map(input).collect()).await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().proof, vec![1]);
+    match &results[1] {
+        Err(BbServiceError::Batch { index: 1, source }) => assert!(matches!(
+            source.as_ref(),
+            BbServiceError::Service {
+                status: 500,
+                details: Some(details),
+                ..
+            } if details == "x out of range"
+        )),
+        other => panic!("expected a batch error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn proof_stream_reports_a_truncated_stream() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove/batch"))
+        .respond_with(ndjson(&[json!({ "proof": [1], "publicInputs": [] })]))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let results = collect_stream(&client, (1..=2).map(input).collect()).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        &results[1],
+        Err(BbServiceError::Batch { index: 1, source })
+            if matches!(source.as_ref(), BbServiceError::InvalidResponse)
+    ));
+}
+
+#[tokio::test]
+async fn proof_stream_accepts_whole_batches_and_falls_back() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove/batch"))
+        .and(body_partial_json(json!({ "inputs": [{ "x": 1 }] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proofs generated successfully",
+            "proofs": [{ "proof": [1], "publicInputs": [] }]
+        })))
+        .mount(&server)
+        .await;
+    let client = BbServiceClient::new(server.uri());
+    let results = collect_stream(&client, vec![input(1)]).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().unwrap().proof, vec![1]);
+
+    let server = MockServer::start().await;
+    for x in 1..=3 {
+        mount_single_prove(
+            &server,
+            x,
+            ResponseTemplate::new(200).set_body_json(proof_body(x as u8)),
+        )
+        .await;
+    }
+    let client = BbServiceClient::new(server.uri());
+    let results = collect_stream(&client, (1..=3).map(input).collect()).await;
+    let bytes: Vec<u8> = results
+        .into_iter()
+        .map(|proof| proof.unwrap().proof[0])
+        .collect();
+    assert_eq!(bytes, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn batch_methods_borrow_or_share_the_circuit() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/verify/batch"))
+        .and(body_partial_json(json!({ "circuit": test_circuit() })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proofs verified",
+            "results": [true]
+        })))
+        .mount(&server)
+        .await;
+    for x in 1..=2 {
+        mount_single_prove(
+            &server,
+            x,
+            ResponseTemplate::new(200).set_body_json(proof_body(x as u8)),
+        )
+        .await;
+    }
+    let client = BbServiceClient::new(server.uri());
+    let circuit = test_circuit();
+
+    let proofs = client
+        .generate_proofs_batch(&circuit, vec![input(1)])
+        .await
+        .unwrap();
+    let proofs = [
+        proofs,
+        client
+            .generate_proofs_concurrent(&circuit, vec![input(2)], 1)
+            .await
+            .unwrap(),
+    ]
+    .concat();
+    assert_eq!(proofs.len(), 2);
+    let results = client
+        .verify_proofs_batch(&circuit, vec![proof(1)])
+        .await
+        .unwrap();
+    assert_eq!(results, vec![true]);
+
+    let shared = Arc::new(circuit);
+    let results: Vec<_> = client
+        .generate_proofs_stream(shared.clone(), vec![input(1), input(2)])
+        .collect()
+        .await;
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use bb_service_rs::blocking::BlockingBbServiceClient;
+use bb_service_rs::{BbServiceClientBuilder, BbServiceError, InputMap, ProofData};
+use common::{prove_body, test_circuit};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blocking_client_proves_verifies_and_checks_health() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .and(header("authorization", "Bearer token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(prove_body()))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/verify"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proof verification completed",
+            "isValid": true
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    tokio::task::spawn_blocking(move || {
+        let client = BbServiceClientBuilder::default()
+            .base_url(uri)
+            .bearer_token("token".to_string())
+            .build_blocking()
+            .unwrap();
+
+        let proof = client
+            .generate_proof(test_circuit(), InputMap::new())
+            .unwrap();
+        assert_eq!(proof.proof, vec![1, 2, 3]);
+        assert!(client.verify_proof(test_circuit(), proof).unwrap());
+        assert!(client.health_check().unwrap());
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blocking_client_reports_service_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/verify"))
+        .respond_with(ResponseTemplate::new(500).set_body_json(json!({
+            "error": "Failed to verify proof",
+            "details": "bb exited with code 1"
+        })))
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    tokio::task::spawn_blocking(move || {
+        let client = BlockingBbServiceClient::new(uri);
+        let proof = ProofData {
+            proof: vec![1],
+            public_inputs: vec![],
+        };
+
+        match client.verify_proof(test_circuit(), proof) {
+            Err(BbServiceError::Service {
+                status: 500,
+                details: Some(details),
+                ..
+            }) => assert_eq!(details, "bb exited with code 1"),
+            other => panic!("expected a service error, got {:?}", other),
+        }
+    })
+    .await
+    .unwrap();
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use bb_service_rs::{BbServiceClient, ProofData};
+use common::test_circuit;
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn proof() -> ProofData {
+    ProofData {
+        proof: vec![1, 2, 3],
+        public_inputs: vec![],
+    }
+}
+
+async fn mount_vk_and_verify(server: &MockServer, vk_requests: u64, verify_requests: u64) {
+    Mock::given(method("POST"))
+        .and(path("/vk"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Verification key generated successfully",
+            "vk": [9, 9]
+        })))
+        .expect(vk_requests)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/verify/vk"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proof verification completed",
+            "isValid": true
+        })))
+        .expect(verify_requests)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn verification_key_is_fetched_once() {
+    let server = MockServer::start().await;
+    mount_vk_and_verify(&server, 1, 3).await;
+
+    let client = BbServiceClient::new(server.uri());
+    for _ in 0..3 {
+        assert!(
+            client
+                .verify_proof_cached(test_circuit(), proof())
+                .await
+                .unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn clearing_the_cache_refetches_the_key() {
+    let server = MockServer::start().await;
+    mount_vk_and_verify(&server, 2, 2).await;
+
+    let client = BbServiceClient::new(server.uri());
+    client
+        .verify_proof_cached(test_circuit(), proof())
+        .await
+        .unwrap();
+    client.clear_vk_cache();
+    client
+        .verify_proof_cached(test_circuit(), proof())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn clones_share_the_cache() {
+    let server = MockServer::start().await;
+    mount_vk_and_verify(&server, 1, 2).await;
+
+    let client = BbServiceClient::new(server.uri());
+    let clone = client.clone();
+    let task = tokio::spawn(async move {
+        clone
+            .verify_proof_cached(test_circuit(), proof())
+            .await
+            .unwrap()
+    });
+    assert!(task.await.unwrap());
+
+    assert!(
+        client
+            .verify_proof_cached(test_circuit(), proof())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn prepared_verifier_reuses_the_key() {
+    let server = MockServer::start().await;
+    mount_vk_and_verify(&server, 1, 3).await;
+
+    let client = BbServiceClient::new(server.uri());
+    let verifier = client.prepare_verifier(test_circuit()).await.unwrap();
+    assert_eq!(verifier.vk(), [9, 9]);
+    drop(client);
+
+    for _ in 0..3 {
+        assert!(verifier.verify(proof()).await.unwrap());
+    }
+    let requests = server.received_requests().await.unwrap();
+    let verify: serde_json::Value = requests.last().unwrap().body_json().unwrap();
+    assert!(verify.get("circuit").is_none());
+}
+
+#[tokio::test]
+async fn prepared_verifier_shares_the_cache() {
+    let server = MockServer::start().await;
+    mount_vk_and_verify(&server, 1, 2).await;
+
+    let client = BbServiceClient::new(server.uri());
+    let verifier = client.prepare_verifier(test_circuit()).await.unwrap();
+    assert!(verifier.verify(proof()).await.unwrap());
+    assert!(
+        client
+            .verify_proof_cached(test_circuit(), proof())
+            .await
+            .unwrap()
+    );
+}
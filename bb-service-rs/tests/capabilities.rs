@@ -0,0 +1,69 @@
+use bb_service_rs::{BbServiceClient, BbServiceError, ProvingBackend, ServiceCapabilities};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client_for(response: ResponseTemplate) -> (MockServer, BbServiceClient) {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/capabilities"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    let client = BbServiceClient::new(server.uri());
+    (server, client)
+}
+
+#[tokio::test]
+async fn capabilities_are_reported() {
+    let (_server, client) = client_for(ResponseTemplate::new(200).set_body_json(json!({
+        "backends": ["ultra_honk"],
+        "maxCircuitSize": 1048576,
+        "features": ["batch", "jobs"]
+    })))
+    .await;
+
+    let capabilities = client.capabilities().await.unwrap();
+    assert_eq!(capabilities.max_circuit_size, Some(1 << 20));
+    assert!(capabilities.supports_backend(ProvingBackend::UltraHonk));
+    assert!(!capabilities.supports_backend(ProvingBackend::UltraPlonk));
+    assert!(capabilities.has_feature("jobs"));
+    assert!(!capabilities.has_feature("streaming"));
+}
+
+#[tokio::test]
+async fn missing_fields_default_to_empty() {
+    let (_server, client) = client_for(
+        ResponseTemplate::new(200).set_body_json(json!({ "backends": ["ultra_plonk"] })),
+    )
+    .await;
+
+    let capabilities = client.capabilities().await.unwrap();
+    assert!(capabilities.supports_backend(ProvingBackend::UltraPlonk));
+    assert_eq!(capabilities.max_circuit_size, None);
+    assert!(capabilities.features.is_empty());
+}
+
+#[tokio::test]
+async fn older_servers_report_unknown_capabilities() {
+    let server = MockServer::start().await;
+    let client = BbServiceClient::new(server.uri());
+
+    assert_eq!(
+        client.capabilities().await.unwrap(),
+        ServiceCapabilities::default()
+    );
+}
+
+#[tokio::test]
+async fn server_errors_are_not_hidden() {
+    let (_server, client) = client_for(
+        ResponseTemplate::new(500).set_body_json(json!({ "error": "Internal server error" })),
+    )
+    .await;
+
+    assert!(matches!(
+        client.capabilities().await,
+        Err(BbServiceError::Service { status: 500, .. })
+    ));
+}
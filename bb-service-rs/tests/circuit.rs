@@ -0,0 +1,409 @@
+mod common;
+
+use bb_service_rs::{
+    BbServiceClient, BbServiceError, InputMap, InvalidCircuitPolicy, ValidationMode, VersionReq,
+    check_compatibility, circuit_bytecode_bytes, circuit_hash, circuit_noir_version,
+    load_circuit_definition, load_circuit_definition_blocking, load_circuit_definition_with,
+    load_circuit_from_reader, load_circuits_from_dir, parse_circuit_definition,
+    parse_circuit_definition_with,
+};
+use common::fixture_path;
+use serde_json::json;
+use std::fs;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn load_circuit_definition_reads_fixture() {
+    let circuit = load_circuit_definition(&fixture_path()).await.unwrap();
+    assert!(circuit["bytecode"].is_string());
+    assert!(circuit["abi"]["parameters"].is_array());
+}
+
+#[test]
+fn blocking_loader_matches_async_loader() {
+    let blocking = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let non_blocking = runtime
+        .block_on(load_circuit_definition(&fixture_path()))
+        .unwrap();
+    assert_eq!(blocking, non_blocking);
+}
+
+#[test]
+fn loader_rejects_missing_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("circuit.json");
+    fs::write(&path, r#"{"bytecode": "H4sIAAAAAAAA/w=="}"#).unwrap();
+
+    let err = load_circuit_definition_blocking(path.to_str().unwrap()).unwrap_err();
+    assert!(matches!(err, BbServiceError::Circuit(_)));
+    assert!(err.to_string().contains("'bytecode' and 'abi'"));
+}
+
+#[tokio::test]
+async fn loaders_report_missing_files_as_io_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing.json");
+    let path = path.to_str().unwrap();
+
+    for err in [
+        load_circuit_definition(path).await.unwrap_err(),
+        load_circuit_definition_blocking(path).unwrap_err(),
+    ] {
+        match err {
+            BbServiceError::Io(err) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+                assert!(err.to_string().contains("missing.json"));
+            }
+            other => panic!("expected an I/O error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn parse_from_bytes_and_reader() {
+    let bytes = fs::read(fixture_path()).unwrap();
+
+    let parsed = parse_circuit_definition(&bytes).unwrap();
+    let from_reader = load_circuit_from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(parsed, from_reader);
+    assert_eq!(
+        parsed,
+        load_circuit_definition_blocking(&fixture_path()).unwrap()
+    );
+}
+
+#[test]
+fn parse_rejects_invalid_circuits() {
+    for bytes in [&b"not json"[..], b"[1, 2]", br#"{"abi": {}}"#] {
+        assert!(matches!(
+            parse_circuit_definition(bytes),
+            Err(BbServiceError::Circuit(_))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn load_circuit_from_url_fetches_and_validates() {
+    let server = MockServer::start().await;
+    let bytes = fs::read(fixture_path()).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/circuits/substring_sha256.json"))
+        .and(header("authorization", "Bearer token"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(bytes.clone()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/circuits/broken.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"abi": {}}"#))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .bearer_token("token".to_string())
+        .build()
+        .unwrap();
+
+    let circuit = client
+        .load_circuit_from_url(&format!("{}/circuits/substring_sha256.json", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(circuit, parse_circuit_definition(&bytes).unwrap());
+
+    let err = client
+        .load_circuit_from_url(&format!("{}/circuits/broken.json", server.uri()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BbServiceError::Circuit(_)));
+
+    let err = client
+        .load_circuit_from_url(&format!("{}/circuits/missing.json", server.uri()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BbServiceError::Service { status: 404, .. }));
+}
+
+#[test]
+fn circuit_hash_depends_only_on_bytecode() {
+    let circuit = json!({ "bytecode": "H4sIAAAAAAAA/w==", "abi": { "parameters": [] } });
+    let with_metadata = json!({
+        "noir_version": "1.0.0-beta.9",
+        "bytecode": " H4sIAAAAAAAA/w==\n",
+        "abi": { "parameters": [], "return_type": null },
+        "debug_symbols": "..."
+    });
+    let other = json!({ "bytecode": "H4sIAAAAAAAA/x==", "abi": { "parameters": [] } });
+
+    let hash = circuit_hash(&circuit).unwrap();
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, circuit_hash(&with_metadata).unwrap());
+    assert_ne!(hash, circuit_hash(&other).unwrap());
+    assert!(circuit_hash(&json!({ "abi": {} })).is_err());
+}
+
+#[test]
+fn circuit_bytecode_is_decoded() {
+    let circuit = json!({ "bytecode": "H4sIAAAAAAAA/w==\n", "abi": { "parameters": [] } });
+    assert_eq!(
+        circuit_bytecode_bytes(&circuit).unwrap(),
+        [0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff]
+    );
+
+    let fixture = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    assert!(
+        circuit_bytecode_bytes(&fixture)
+            .unwrap()
+            .starts_with(&[0x1f, 0x8b])
+    );
+
+    for invalid in [json!({ "bytecode": "not base64!" }), json!({ "abi": {} })] {
+        assert!(matches!(
+            circuit_bytecode_bytes(&invalid),
+            Err(BbServiceError::Circuit(_))
+        ));
+    }
+}
+
+#[test]
+fn noir_version_is_read_from_the_artifact() {
+    let fixture = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    assert_eq!(
+        circuit_noir_version(&fixture).as_deref(),
+        Some("1.0.0-beta.9+6abff2f16e1c1314ba30708d1cf032a536de3d19")
+    );
+    assert_eq!(circuit_noir_version(&json!({ "bytecode": "" })), None);
+}
+
+#[test]
+fn compatibility_is_checked_against_the_supported_range() {
+    let fixture = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    let supported = |req: &str| VersionReq::parse(req).unwrap();
+
+    assert_eq!(
+        check_compatibility(&fixture, &supported(">=1.0.0-beta.3, <2")),
+        None
+    );
+
+    let warning = check_compatibility(&fixture, &supported(">=1.0.0")).unwrap();
+    assert!(warning.contains("1.0.0-beta.9"), "{}", warning);
+    assert!(check_compatibility(&json!({ "bytecode": "" }), &supported("*")).is_some());
+    assert!(check_compatibility(&json!({ "noir_version": "nightly" }), &supported("*")).is_some());
+}
+
+#[tokio::test]
+async fn strict_validation_accepts_nargo_artifacts() {
+    let path = fixture_path();
+    let strict = load_circuit_definition_with(&path, ValidationMode::Strict)
+        .await
+        .unwrap();
+    assert_eq!(strict, load_circuit_definition(&path).await.unwrap());
+}
+
+#[test]
+fn strict_validation_pinpoints_the_first_problem() {
+    let field = json!({ "kind": "field" });
+    let cases = [
+        (
+            json!({ "bytecode": "", "abi": { "parameters": [] } }),
+            "bytecode",
+        ),
+        (json!({ "bytecode": "H4sI", "abi": [] }), "abi:"),
+        (json!({ "bytecode": "H4sI", "abi": {} }), "abi.parameters:"),
+        (
+            json!({ "bytecode": "H4sI", "abi": { "parameters": [
+                { "name": "x", "type": field, "visibility": "private" },
+                { "name": "y", "type": { "kind": "float" }, "visibility": "public" }
+            ] } }),
+            "abi.parameters[1] (y)",
+        ),
+        (
+            json!({ "bytecode": "H4sI", "abi": {
+                "parameters": [],
+                "return_type": { "abi_type": field }
+            } }),
+            "abi.return_type",
+        ),
+    ];
+
+    for (circuit, at) in cases {
+        let bytes = serde_json::to_vec(&circuit).unwrap();
+        assert!(parse_circuit_definition_with(&bytes, ValidationMode::Lenient).is_ok());
+        match parse_circuit_definition_with(&bytes, ValidationMode::Strict) {
+            Err(BbServiceError::Circuit(message)) => {
+                assert!(message.contains(at), "{} does not mention {}", message, at)
+            }
+            other => panic!("expected a circuit error, got {:?}", other),
+        }
+    }
+
+    let invalid_base64 = json!({ "bytecode": "not base64!", "abi": { "parameters": [] } });
+    assert!(matches!(
+        parse_circuit_definition_with(
+            &serde_json::to_vec(&invalid_base64).unwrap(),
+            ValidationMode::Strict
+        ),
+        Err(BbServiceError::Circuit(_))
+    ));
+}
+
+#[tokio::test]
+async fn prove_from_file_loads_then_proves() {
+    let server = MockServer::start().await;
+    let circuit = load_circuit_definition_blocking(&fixture_path()).unwrap();
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .and(body_partial_json(json!({ "circuit": circuit })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::prove_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let proof = client
+        .prove_from_file(&fixture_path(), InputMap::new())
+        .await
+        .unwrap();
+    assert_eq!(proof.proof, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn prove_from_file_reports_file_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let invalid = dir.path().join("invalid.json");
+    fs::write(&invalid, r#"{"abi": {}}"#).unwrap();
+    let missing = dir.path().join("missing.json");
+    let client = BbServiceClient::new("http://localhost:3000");
+
+    match client
+        .prove_from_file(missing.to_str().unwrap(), InputMap::new())
+        .await
+    {
+        Err(BbServiceError::Io(err)) => {
+            assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+            assert!(err.to_string().contains("missing.json"));
+        }
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+    assert!(matches!(
+        client
+            .prove_from_file(invalid.to_str().unwrap(), InputMap::new())
+            .await,
+        Err(BbServiceError::Circuit(_))
+    ));
+}
+
+#[tokio::test]
+async fn directory_loader_keys_circuits_by_stem() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::copy(fixture_path(), dir.path().join("substring.json")).unwrap();
+    fs::write(
+        dir.path().join("minimal.json"),
+        r#"{"bytecode": "H4sI", "abi": {}}"#,
+    )
+    .unwrap();
+    fs::write(dir.path().join("notes.txt"), "not a circuit").unwrap();
+    fs::create_dir(dir.path().join("nested.json")).unwrap();
+    fs::write(dir.path().join("broken.json"), r#"{"abi": {}}"#).unwrap();
+    let path = dir.path().to_str().unwrap();
+
+    let circuits = load_circuits_from_dir(path, InvalidCircuitPolicy::Skip)
+        .await
+        .unwrap();
+    let mut names: Vec<_> = circuits.keys().cloned().collect();
+    names.sort();
+    assert_eq!(names, ["minimal", "substring"]);
+    assert_eq!(
+        circuits["substring"],
+        load_circuit_definition_blocking(&fixture_path()).unwrap()
+    );
+
+    let err = load_circuits_from_dir(path, InvalidCircuitPolicy::Fail)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Invalid circuit: {}: Circuit JSON must contain 'bytecode' and 'abi' fields",
+            dir.path().join("broken.json").display()
+        )
+    );
+}
+
+#[tokio::test]
+async fn directory_loader_reports_missing_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+
+    assert!(matches!(
+        load_circuits_from_dir(missing.to_str().unwrap(), InvalidCircuitPolicy::Skip).await,
+        Err(BbServiceError::Io(_))
+    ));
+}
+
+/// The fixture artifact, gzipped
+fn gzipped_fixture() -> Vec<u8> {
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&fs::read(fixture_path()).unwrap())
+        .unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn gzipped_circuits_are_decompressed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("substring.json.gz");
+    fs::write(&path, gzipped_fixture()).unwrap();
+    let expected = load_circuit_definition_blocking(&fixture_path()).unwrap();
+
+    assert_eq!(
+        load_circuit_definition(path.to_str().unwrap())
+            .await
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        parse_circuit_definition(&gzipped_fixture()).unwrap(),
+        expected
+    );
+
+    let circuits = load_circuits_from_dir(dir.path().to_str().unwrap(), InvalidCircuitPolicy::Fail)
+        .await
+        .unwrap();
+    assert_eq!(circuits["substring"], expected);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzipped_circuits_are_validated() {
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(br#"{"abi": {}}"#).unwrap();
+    let missing_bytecode = encoder.finish().unwrap();
+    let mut truncated = gzipped_fixture();
+    truncated.truncate(truncated.len() / 2);
+
+    for bytes in [missing_bytecode, truncated] {
+        assert!(matches!(
+            parse_circuit_definition(&bytes),
+            Err(BbServiceError::Circuit(_))
+        ));
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn gzipped_circuits_need_the_gzip_feature() {
+    match parse_circuit_definition(&gzipped_fixture()) {
+        Err(BbServiceError::Circuit(message)) => assert!(message.contains("`gzip` feature")),
+        other => panic!("expected a circuit error, got {:?}", other),
+    }
+}
@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+
+use bb_service_rs::CompiledCircuit;
+use serde_json::json;
+use std::io::Read;
+use std::net::TcpStream;
+
+/// Minimal circuit artifact accepted by the client
+pub fn test_circuit() -> CompiledCircuit {
+    json!({
+        "bytecode": "H4sIAAAAAAAA/w==",
+        "abi": { "parameters": [], "return_type": null }
+    })
+}
+
+/// Successful `/prove` response body
+pub fn prove_body() -> serde_json::Value {
+    json!({
+        "message": "Proof generated successfully",
+        "proof": { "proof": [1, 2, 3], "publicInputs": [4, 5, 6] }
+    })
+}
+
+/// Path to the compiled circuit fixture shared with the service tests
+pub fn fixture_path() -> String {
+    format!(
+        "{}/../fixture/substring_sha256.json",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+/// Read a whole HTTP/1.1 request with a `content-length` body
+pub fn read_request(stream: &mut TcpStream) {
+    let mut request = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&request[..end]).to_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            if request.len() >= end + 4 + length {
+                return;
+            }
+        }
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Kept in its own test binary with a single test, since the process
+//! environment is shared between threads.
+
+use bb_service_rs::{BB_SERVICE_TOKEN_ENV, BB_SERVICE_URL_ENV, BbServiceClient, BbServiceError};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn set_env(url: Option<&str>, token: Option<&str>) {
+    // SAFETY: this is the only test in the binary, nothing reads the
+    // environment concurrently.
+    unsafe {
+        match url {
+            Some(url) => std::env::set_var(BB_SERVICE_URL_ENV, url),
+            None => std::env::remove_var(BB_SERVICE_URL_ENV),
+        }
+        match token {
+            Some(token) => std::env::set_var(BB_SERVICE_TOKEN_ENV, token),
+            None => std::env::remove_var(BB_SERVICE_TOKEN_ENV),
+        }
+    }
+}
+
+#[tokio::test]
+async fn client_is_configured_from_env() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("authorization", "Bearer env-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "ok" })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    set_env(Some(&server.uri()), Some("env-token"));
+    let client = BbServiceClient::from_env().unwrap();
+    assert!(client.health_check().await.unwrap());
+
+    set_env(None, Some(""));
+    assert!(BbServiceClient::from_env().is_ok());
+
+    set_env(Some("localhost:3000"), None);
+    assert!(matches!(
+        BbServiceClient::from_env(),
+        Err(BbServiceError::InvalidUrl(_))
+    ));
+
+    // SAFETY: as above
+    unsafe { std::env::set_var("HTTP_PROXY", server.uri()) };
+    set_env(Some("http://bb-service.invalid"), Some("env-token"));
+    let client = BbServiceClient::from_env().unwrap();
+    assert!(client.health_check().await.unwrap());
+
+    // SAFETY: as above
+    unsafe { std::env::remove_var("HTTP_PROXY") };
+    set_env(None, None);
+}
@@ -0,0 +1,206 @@
+use bb_service_rs::{BbServiceClient, BbServiceError, ErrorCode};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn service(status: u16) -> BbServiceError {
+    BbServiceError::Service {
+        status,
+        error: "error".to_string(),
+        details: None,
+        code: None,
+        request_id: None,
+    }
+}
+
+fn batch(source: BbServiceError) -> BbServiceError {
+    BbServiceError::Batch {
+        index: 0,
+        source: Box::new(source),
+    }
+}
+
+#[test]
+fn retryable_errors_are_classified() {
+    let cases = [
+        (service(400), false),
+        (service(404), false),
+        (service(422), false),
+        (service(500), true),
+        (service(503), true),
+        (BbServiceError::RateLimited { retry_after: None }, true),
+        (
+            BbServiceError::RateLimited {
+                retry_after: Some(Duration::from_secs(1)),
+            },
+            true,
+        ),
+        (BbServiceError::InvalidResponse, false),
+        (
+            BbServiceError::MalformedResponse {
+                status: 200,
+                length: 0,
+                reason: "empty body".to_string(),
+            },
+            false,
+        ),
+        (BbServiceError::JobFailed("failed".to_string()), false),
+        (BbServiceError::InvalidProof("short".to_string()), false),
+        (BbServiceError::InvalidUrl("ftp://host".to_string()), false),
+        (BbServiceError::InvalidInput("missing".to_string()), false),
+        (BbServiceError::Circuit("no abi".to_string()), false),
+        (
+            BbServiceError::InvalidConfig("bad header".to_string()),
+            false,
+        ),
+        (
+            BbServiceError::ResponseTooLarge {
+                status: 200,
+                limit: 1024,
+            },
+            false,
+        ),
+        (BbServiceError::VersionUnknown, false),
+        (
+            BbServiceError::VersionMismatch {
+                expected: "^1.2".to_string(),
+                actual: "1.1.0".to_string(),
+            },
+            false,
+        ),
+        (BbServiceError::Cancelled, false),
+        (batch(service(502)), true),
+        (batch(service(400)), false),
+    ];
+
+    for (err, retryable) in cases {
+        assert_eq!(err.is_retryable(), retryable, "{}", err);
+    }
+}
+
+#[tokio::test]
+async fn timeouts_are_retryable() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::with_timeout(server.uri(), Duration::from_millis(50));
+    let err = client.health_check().await.unwrap_err();
+    assert!(matches!(err, BbServiceError::Timeout { .. }));
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn connection_errors_are_retryable() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let client = BbServiceClient::new(format!("http://127.0.0.1:{}", port));
+    let err = client.health_check().await.unwrap_err();
+    assert!(matches!(err, BbServiceError::Connection(_)));
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn error_codes_are_exposed() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(413).set_body_json(json!({
+            "error": "Circuit too large",
+            "code": "CIRCUIT_TOO_LARGE"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/verify"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "Proof is malformed",
+            "code": "PROOF_TRUNCATED"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let err = client
+        .generate_proof(json!({}), Default::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        BbServiceError::Service { code: Some(code), .. } if code == "CIRCUIT_TOO_LARGE"
+    ));
+    assert_eq!(err.code(), Some(ErrorCode::CircuitTooLarge));
+
+    let proof = bb_service_rs::ProofData {
+        proof: vec![1],
+        public_inputs: vec![],
+    };
+    let err = client.verify_proof(json!({}), proof).await.unwrap_err();
+    assert_eq!(
+        err.code(),
+        Some(ErrorCode::Other("PROOF_TRUNCATED".to_string()))
+    );
+}
+
+#[test]
+fn error_codes_round_trip() {
+    for code in [
+        "CIRCUIT_TOO_LARGE",
+        "INVALID_CIRCUIT",
+        "INVALID_WITNESS",
+        "INVALID_PROOF",
+        "UNSUPPORTED_BACKEND",
+        "SOMETHING_NEW",
+    ] {
+        assert_eq!(ErrorCode::from(code).as_str(), code);
+    }
+    assert_eq!(
+        ErrorCode::from("INVALID_WITNESS"),
+        ErrorCode::InvalidWitness
+    );
+    assert_eq!(service(500).code(), None);
+    assert_eq!(
+        batch(BbServiceError::Service {
+            status: 400,
+            error: "error".to_string(),
+            details: None,
+            code: Some("INVALID_WITNESS".to_string()),
+            request_id: None,
+        })
+        .code(),
+        Some(ErrorCode::InvalidWitness)
+    );
+}
+
+#[tokio::test]
+async fn error_responses_carry_the_request_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(
+            ResponseTemplate::new(500)
+                .insert_header("x-request-id", "req-1234")
+                .set_body_json(json!({ "error": "Proof generation failed" })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let err = client
+        .generate_proof(json!({}), Default::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.request_id(), Some("req-1234"));
+    assert_eq!(
+        err.to_string(),
+        "Service error (500): Proof generation failed (request id req-1234)"
+    );
+    assert_eq!(batch(err).request_id(), Some("req-1234"));
+    assert_eq!(service(500).request_id(), None);
+}
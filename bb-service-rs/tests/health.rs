@@ -0,0 +1,148 @@
+use bb_service_rs::{BbServiceClient, BbServiceError, HealthStatus, VersionReq};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client_for(response: ResponseTemplate) -> (MockServer, BbServiceClient) {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    let client = BbServiceClient::new(server.uri());
+    (server, client)
+}
+
+#[tokio::test]
+async fn health_status_reports_details() {
+    let (_server, client) = client_for(ResponseTemplate::new(200).set_body_json(json!({
+        "status": "healthy",
+        "timestamp": "2025-08-29T10:30:00.000Z",
+        "service": "bb-service",
+        "version": "1.2.0",
+        "backend": "ultra_honk"
+    })))
+    .await;
+
+    let status = client.health_status().await.unwrap();
+    assert_eq!(status.status.as_deref(), Some("healthy"));
+    assert_eq!(status.version.as_deref(), Some("1.2.0"));
+    assert_eq!(status.backend.as_deref(), Some("ultra_honk"));
+    assert_eq!(status.service.as_deref(), Some("bb-service"));
+    assert!(client.health_check().await.unwrap());
+}
+
+#[tokio::test]
+async fn health_status_tolerates_missing_fields_and_empty_body() {
+    let (_server, client) =
+        client_for(ResponseTemplate::new(200).set_body_json(json!({ "status": "healthy" }))).await;
+    let status = client.health_status().await.unwrap();
+    assert_eq!(status.version, None);
+
+    for response in [
+        ResponseTemplate::new(200),
+        ResponseTemplate::new(200).set_body_string(" \n"),
+    ] {
+        let (_server, client) = client_for(response).await;
+        assert_eq!(
+            client.health_status().await.unwrap(),
+            HealthStatus::default()
+        );
+        assert!(client.health_check().await.unwrap());
+    }
+}
+
+#[tokio::test]
+async fn health_check_accepts_bodies_without_details() {
+    for response in [
+        ResponseTemplate::new(200).set_body_string("OK"),
+        ResponseTemplate::new(200).set_body_raw("<html></html>", "text/html"),
+    ] {
+        let (_server, client) = client_for(response).await;
+        assert!(client.health_check().await.unwrap());
+        assert!(matches!(
+            client.health_status().await,
+            Err(BbServiceError::MalformedResponse { status: 200, .. })
+        ));
+    }
+}
+
+#[tokio::test]
+async fn unhealthy_service_is_an_error_status() {
+    let (_server, client) = client_for(ResponseTemplate::new(503)).await;
+
+    assert!(matches!(
+        client.health_status().await,
+        Err(BbServiceError::Service { status: 503, .. })
+    ));
+    assert!(!client.health_check().await.unwrap());
+}
+
+#[tokio::test]
+async fn ping_measures_round_trip() {
+    let (_server, client) =
+        client_for(ResponseTemplate::new(200).set_delay(Duration::from_millis(100))).await;
+
+    let elapsed = client.ping().await.unwrap();
+    assert!(elapsed >= Duration::from_millis(100));
+    assert!(elapsed < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn ping_fails_for_error_status() {
+    let (_server, client) = client_for(ResponseTemplate::new(500)).await;
+    assert!(matches!(
+        client.ping().await,
+        Err(BbServiceError::Service { status: 500, .. })
+    ));
+}
+
+#[tokio::test]
+async fn assert_version_accepts_a_matching_version() {
+    let expected = VersionReq::parse("^1.2").unwrap();
+    for version in ["1.2.0", "v1.3.1"] {
+        let (_server, client) =
+            client_for(ResponseTemplate::new(200).set_body_json(json!({ "version": version })))
+                .await;
+        client.assert_version(&expected).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn assert_version_rejects_other_versions() {
+    let expected = VersionReq::parse("^1.2").unwrap();
+    for version in ["1.1.9", "2.0.0", "nightly"] {
+        let (_server, client) =
+            client_for(ResponseTemplate::new(200).set_body_json(json!({ "version": version })))
+                .await;
+        match client.assert_version(&expected).await {
+            Err(BbServiceError::VersionMismatch {
+                expected: requirement,
+                actual,
+            }) => {
+                assert_eq!(requirement, "^1.2");
+                assert_eq!(actual, version);
+            }
+            other => panic!("expected a version mismatch, got {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn assert_version_reports_a_missing_version() {
+    let expected = VersionReq::parse("^1.2").unwrap();
+    let (_server, client) =
+        client_for(ResponseTemplate::new(200).set_body_json(json!({ "status": "healthy" }))).await;
+    assert!(matches!(
+        client.assert_version(&expected).await,
+        Err(BbServiceError::VersionUnknown)
+    ));
+
+    let (_server, client) = client_for(ResponseTemplate::new(503)).await;
+    assert!(matches!(
+        client.assert_version(&expected).await,
+        Err(BbServiceError::Service { status: 503, .. })
+    ));
+}
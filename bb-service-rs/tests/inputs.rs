@@ -0,0 +1,306 @@
+use bb_service_rs::{
+    BbServiceClient, BbServiceError, CompiledCircuit, InputBuilder, InputMap, hex_to_field_value,
+    to_field_value, validate_inputs,
+};
+use futures::StreamExt;
+use serde_json::json;
+use wiremock::MockServer;
+
+fn circuit() -> CompiledCircuit {
+    json!({
+        "bytecode": "H4sIAAAAAAAA/w==",
+        "abi": {
+            "parameters": [
+                { "name": "x", "type": { "kind": "field" }, "visibility": "private" },
+                { "name": "y", "type": { "kind": "field" }, "visibility": "public" }
+            ],
+            "return_type": null
+        }
+    })
+}
+
+fn input(keys: &[&str]) -> InputMap {
+    keys.iter()
+        .map(|key| (key.to_string(), json!("1")))
+        .collect()
+}
+
+#[test]
+fn matching_inputs_are_valid() {
+    assert!(validate_inputs(&circuit(), &input(&["x", "y"])).is_ok());
+}
+
+#[test]
+fn missing_and_unknown_inputs_are_listed() {
+    match validate_inputs(&circuit(), &input(&["y", "z", "w"])) {
+        Err(BbServiceError::InvalidInput(message)) => {
+            assert_eq!(message, "missing inputs: x; unknown inputs: w, z")
+        }
+        other => panic!("expected invalid inputs, got {:?}", other),
+    }
+}
+
+/// A circuit taking `foo: Foo { bar: [Field; 3], flag: bool }` and
+/// `pair: (u8, str<2>)`
+fn nested_circuit() -> CompiledCircuit {
+    json!({
+        "bytecode": "H4sIAAAAAAAA/w==",
+        "abi": {
+            "parameters": [
+                {
+                    "name": "foo",
+                    "type": {
+                        "kind": "struct",
+                        "path": "Foo",
+                        "fields": [
+                            {
+                                "name": "bar",
+                                "type": { "kind": "array", "length": 3, "type": { "kind": "field" } }
+                            },
+                            { "name": "flag", "type": { "kind": "boolean" } }
+                        ]
+                    },
+                    "visibility": "private"
+                },
+                {
+                    "name": "pair",
+                    "type": {
+                        "kind": "tuple",
+                        "fields": [
+                            { "kind": "integer", "sign": "unsigned", "width": 8 },
+                            { "kind": "string", "length": 2 }
+                        ]
+                    },
+                    "visibility": "public"
+                }
+            ],
+            "return_type": null
+        }
+    })
+}
+
+fn nested_input(foo_input: serde_json::Value, pair: serde_json::Value) -> InputMap {
+    InputMap::from_iter([("foo".to_string(), foo_input), ("pair".to_string(), pair)])
+}
+
+#[test]
+fn nested_inputs_matching_the_abi_are_valid() {
+    let input = nested_input(
+        json!({ "bar": ["1", 2, "0x3"], "flag": true }),
+        json!([7, "hi"]),
+    );
+    assert!(validate_inputs(&nested_circuit(), &input).is_ok());
+}
+
+#[test]
+fn shape_mismatches_report_their_path() {
+    let cases = [
+        (
+            nested_input(json!({ "bar": ["1", "2"], "flag": true }), json!([7, "hi"])),
+            "foo.bar: expected an array of 3 elements, got an array of 2 elements",
+        ),
+        (
+            nested_input(
+                json!({ "bar": ["1", "2", ["3"]], "flag": true }),
+                json!([7, "hi"]),
+            ),
+            "foo.bar[2]: expected a number or numeric string, got an array of 1 elements",
+        ),
+        (
+            nested_input(json!({ "bar": ["1", "2", "3"] }), json!([7, "hi"])),
+            "foo.flag: missing field",
+        ),
+        (
+            nested_input(json!(["1", "2", "3"]), json!([7, "hi"])),
+            "foo: expected a struct, got an array of 3 elements",
+        ),
+        (
+            nested_input(
+                json!({ "bar": ["1", "2", "3"], "flag": "yes" }),
+                json!([7, "hi"]),
+            ),
+            "foo.flag: expected a boolean, got a string of 3 bytes",
+        ),
+        (
+            nested_input(
+                json!({ "bar": ["1", "2", "3"], "flag": true }),
+                json!([7, "hey"]),
+            ),
+            "pair.1: expected a string of 2 bytes, got a string of 3 bytes",
+        ),
+        (
+            nested_input(json!({ "bar": ["1", "2", "3"], "flag": true }), json!([7])),
+            "pair: expected a tuple of 2 elements, got an array of 1 elements",
+        ),
+    ];
+
+    for (input, expected) in cases {
+        match validate_inputs(&nested_circuit(), &input) {
+            Err(BbServiceError::InvalidInput(message)) => assert_eq!(message, expected),
+            other => panic!("expected invalid inputs for {}, got {:?}", expected, other),
+        }
+    }
+}
+
+#[test]
+fn parameters_without_a_known_type_only_need_to_be_present() {
+    let circuit = json!({
+        "bytecode": "",
+        "abi": { "parameters": [{ "name": "x" }, { "name": "y", "type": { "kind": "map" } }] }
+    });
+    let input = InputMap::from_iter([
+        ("x".to_string(), json!([[1], { "a": null }])),
+        ("y".to_string(), json!(null)),
+    ]);
+    assert!(validate_inputs(&circuit, &input).is_ok());
+}
+
+#[test]
+fn circuit_without_parameters_is_an_error() {
+    let result = validate_inputs(&json!({ "bytecode": "" }), &InputMap::new());
+    assert!(matches!(result, Err(BbServiceError::Circuit(_))));
+}
+
+#[tokio::test]
+async fn strict_client_rejects_inputs_before_sending() {
+    let server = MockServer::start().await;
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .strict_inputs(true)
+        .build()
+        .unwrap();
+
+    let result = client.generate_proof(circuit(), input(&["x"])).await;
+    assert!(matches!(result, Err(BbServiceError::InvalidInput(_))));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn strict_client_rejects_batch_inputs_before_sending() {
+    let server = MockServer::start().await;
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .strict_inputs(true)
+        .build()
+        .unwrap();
+
+    let result = client
+        .generate_proofs_batch(circuit(), vec![input(&["x", "y"]), input(&["x"])])
+        .await;
+    match result {
+        Err(BbServiceError::Batch { index, source }) => {
+            assert_eq!(index, 1);
+            assert!(matches!(*source, BbServiceError::InvalidInput(_)));
+        }
+        other => panic!("expected a batch error, got {:?}", other),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn strict_client_rejects_streamed_batch_inputs_before_sending() {
+    let server = MockServer::start().await;
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .strict_inputs(true)
+        .build()
+        .unwrap();
+
+    let results: Vec<_> = client
+        .generate_proofs_stream(circuit(), vec![input(&["x"]), input(&["x", "y"])])
+        .collect()
+        .await;
+    match results.as_slice() {
+        [Err(BbServiceError::Batch { index: 0, source })] => {
+            assert!(matches!(**source, BbServiceError::InvalidInput(_)));
+        }
+        other => panic!("expected one batch error, got {:?}", other),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[test]
+fn builder_serializes_each_helper() {
+    let input = InputBuilder::new()
+        .field("length", u64::MAX)
+        .hex("salt", "1f")
+        .hex("prefixed", "0xab")
+        .bool("strict", true)
+        .array("needle", vec![104u8, 105])
+        .value("point", json!({ "x": "1", "y": "2" }))
+        .build();
+
+    assert_eq!(input.len(), 6);
+    assert_eq!(input["length"], json!("18446744073709551615"));
+    assert_eq!(input["salt"], json!("0x1f"));
+    assert_eq!(input["prefixed"], json!("0xab"));
+    assert_eq!(input["strict"], json!(true));
+    assert_eq!(input["needle"], json!([104, 105]));
+    assert_eq!(input["point"], json!({ "x": "1", "y": "2" }));
+}
+
+#[test]
+fn builder_output_matches_the_abi() {
+    let input = InputBuilder::new().field("x", 1).hex("y", "0x2").build();
+    assert!(validate_inputs(&circuit(), &input).is_ok());
+}
+
+#[test]
+fn field_values_use_the_canonical_encoding() {
+    assert_eq!(to_field_value(0u8), json!("0"));
+    assert_eq!(to_field_value(u64::MAX), json!("18446744073709551615"));
+    assert_eq!(
+        to_field_value(u128::MAX),
+        json!("340282366920938463463374607431768211455")
+    );
+
+    assert_eq!(hex_to_field_value("1f").unwrap(), json!("0x1f"));
+    assert_eq!(hex_to_field_value("0x00AB").unwrap(), json!("0xab"));
+    assert_eq!(hex_to_field_value("0X0").unwrap(), json!("0x0"));
+    assert_eq!(
+        hex_to_field_value("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000")
+            .unwrap(),
+        json!("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000")
+    );
+}
+
+#[test]
+fn hex_field_values_are_checked() {
+    for hex in [
+        "",
+        "0x",
+        "0xzz",
+        "12 34",
+        "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        "0x4000000000000000000000000000000000000000000000000000000000000000",
+        "0x10000000000000000000000000000000000000000000000000000000000000000",
+    ] {
+        assert!(
+            matches!(
+                hex_to_field_value(hex),
+                Err(BbServiceError::InvalidInput(_))
+            ),
+            "{:?}",
+            hex
+        );
+    }
+
+    let padded = format!("0x{}1ff", "0".repeat(70));
+    assert_eq!(hex_to_field_value(&padded).unwrap(), json!("0x1ff"));
+}
+
+#[test]
+fn builder_uses_the_canonical_encoding() {
+    let input = InputBuilder::new()
+        .field_u128("big", u128::MAX)
+        .try_hex("salt", "0x00FF")
+        .unwrap()
+        .build();
+    assert_eq!(input["big"], to_field_value(u128::MAX));
+    assert_eq!(input["salt"], json!("0xff"));
+
+    assert!(matches!(
+        InputBuilder::new().try_hex("salt", "0xg"),
+        Err(BbServiceError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use bb_service_rs::{
+    BbServiceClient, BbServiceError, CancellationToken, InputMap, JobId, JobStatus,
+};
+use common::test_circuit;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_submit(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/prove/jobs"))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({ "jobId": "job-1" })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn submit_and_wait_for_proof() {
+    let server = MockServer::start().await;
+    mount_submit(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/prove/jobs/job-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "pending" })))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/prove/jobs/job-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "running" })))
+        .up_to_n_times(1)
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/prove/jobs/job-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "done",
+            "proof": { "proof": [7, 7], "publicInputs": [] }
+        })))
+        .with_priority(3)
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let job = client
+        .submit_proof_job(test_circuit(), InputMap::new())
+        .await
+        .unwrap();
+    assert_eq!(job.as_str(), "job-1");
+
+    assert!(matches!(
+        client.poll_proof_job(&job).await.unwrap(),
+        JobStatus::Pending
+    ));
+
+    let proof = client
+        .wait_for_proof(&job, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert_eq!(proof.proof, vec![7, 7]);
+}
+
+#[tokio::test]
+async fn failed_job_is_an_error() {
+    let server = MockServer::start().await;
+    mount_submit(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/prove/jobs/job-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "failed",
+            "error": "witness generation failed"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let job = client
+        .submit_proof_job(test_circuit(), InputMap::new())
+        .await
+        .unwrap();
+
+    match client.wait_for_proof(&job, Duration::from_millis(10)).await {
+        Err(BbServiceError::JobFailed(reason)) => assert_eq!(reason, "witness generation failed"),
+        other => panic!("expected a failed job, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn cancelled_wait_cancels_the_job() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/prove/jobs/job-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "running" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/prove/jobs/job-1/cancel"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::new(server.uri());
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        canceller.cancel();
+    });
+
+    let result = client
+        .wait_for_proof_cancellable(&"job-1".parse().unwrap(), Duration::from_millis(10), token)
+        .await;
+    assert!(matches!(result, Err(BbServiceError::Cancelled)));
+}
+
+#[test]
+fn job_id_is_validated() {
+    let job: JobId = "job-1".parse().unwrap();
+    assert_eq!(job.to_string(), "job-1");
+    assert_eq!(serde_json::to_value(&job).unwrap(), json!("job-1"));
+    assert_eq!(
+        serde_json::from_value::<JobId>(json!("job-1")).unwrap(),
+        job
+    );
+
+    for invalid in [
+        "", "job 1", "../jobs", "job?id", "job#1", ".", "..", "..\\jobs", "%2e%2e",
+    ] {
+        assert!(
+            matches!(
+                invalid.parse::<JobId>(),
+                Err(BbServiceError::InvalidInput(_))
+            ),
+            "{}",
+            invalid
+        );
+    }
+    for valid in ["...", ".job", "job.1"] {
+        assert_eq!(valid.parse::<JobId>().unwrap().as_str(), valid);
+    }
+    assert!(serde_json::from_value::<JobId>(json!("")).is_err());
+}
+
+#[tokio::test]
+async fn invalid_job_id_from_service_is_an_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove/jobs"))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({ "jobId": "" })))
+        .mount(&server)
+        .await;
+
+    let result = BbServiceClient::new(server.uri())
+        .submit_proof_job(test_circuit(), InputMap::new())
+        .await;
+    assert!(matches!(
+        result,
+        Err(BbServiceError::MalformedResponse { status: 202, .. })
+    ));
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "log")]
+
+mod common;
+
+use bb_service_rs::{BbServiceClient, InputMap};
+use common::{prove_body, test_circuit};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Collects log lines from this crate in memory
+struct Capture(Mutex<Vec<(Level, String)>>);
+
+impl Log for Capture {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target().starts_with("bb_service_rs") {
+            let line = record.args().to_string();
+            self.0.lock().unwrap().push((record.level(), line));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn requests_retries_and_failures_are_logged_without_secrets() {
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(prove_body()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let base_url = server.uri().replace("http://", "http://user:hunter2@");
+    let client = BbServiceClient::builder()
+        .base_url(base_url)
+        .bearer_token("secret-token".to_string())
+        .max_retries(1)
+        .base_delay(Duration::from_millis(1))
+        .build()
+        .unwrap();
+    client
+        .generate_proof(test_circuit(), InputMap::new())
+        .await
+        .unwrap();
+    assert!(!client.health_check().await.unwrap());
+
+    let lines = CAPTURE.0.lock().unwrap().clone();
+    let prove_url = format!("{}/prove", server.uri());
+    assert!(
+        lines.iter().any(|(level, line)| *level == Level::Debug
+            && *line == format!("Sending POST {}", prove_url))
+    );
+    assert!(lines.iter().any(|(level, line)| *level == Level::Debug
+        && line.starts_with(&format!("Retrying {} in", prove_url))
+        && line.contains("status 503")));
+    assert!(lines.iter().any(|(level, line)| *level == Level::Warn
+        && *line
+            == format!(
+                "Request to {}/health failed with status 500 Internal Server Error",
+                server.uri()
+            )));
+    // The prove succeeded after its retry, so only the health check warns
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|(level, _)| *level == Level::Warn)
+            .count(),
+        1
+    );
+
+    for (_, line) in &lines {
+        assert!(!line.contains("secret-token"), "{}", line);
+        assert!(!line.contains("hunter2"), "{}", line);
+        assert!(!line.contains("bytecode"), "{}", line);
+    }
+}
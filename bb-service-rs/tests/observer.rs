@@ -0,0 +1,135 @@
+mod common;
+
+use bb_service_rs::{BbServiceClient, BbServiceError, InputMap, RequestObserver};
+use common::{prove_body, test_circuit};
+use reqwest::StatusCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// What an observer saw, in order
+#[derive(Debug, PartialEq)]
+enum Event {
+    Start,
+    Success(StatusCode),
+    Error(String),
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Event>>>);
+
+impl RequestObserver for Recorder {
+    fn on_start(&self) {
+        self.0.lock().unwrap().push(Event::Start);
+    }
+
+    fn on_success(&self, status: StatusCode, elapsed: Duration) {
+        assert!(elapsed < Duration::from_secs(5));
+        self.0.lock().unwrap().push(Event::Success(status));
+    }
+
+    fn on_error(&self, error: &BbServiceError) {
+        self.0.lock().unwrap().push(Event::Error(error.to_string()));
+    }
+}
+
+impl Recorder {
+    fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+#[tokio::test]
+async fn observer_sees_each_request_once() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(prove_body()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let recorder = Recorder::default();
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .max_retries(1)
+        .base_delay(Duration::from_millis(1))
+        .observer(recorder.clone())
+        .build()
+        .unwrap();
+
+    client
+        .generate_proof(test_circuit(), InputMap::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        recorder.take(),
+        [Event::Start, Event::Success(StatusCode::OK)]
+    );
+
+    // Error statuses are still answers from the service
+    assert!(!client.health_check().await.unwrap());
+    assert_eq!(
+        recorder.take(),
+        [
+            Event::Start,
+            Event::Success(StatusCode::INTERNAL_SERVER_ERROR)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn observer_sees_failures() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let recorder = Recorder::default();
+    let client = BbServiceClient::builder()
+        .base_url(format!("http://127.0.0.1:{}", port))
+        .observer(recorder.clone())
+        .build()
+        .unwrap();
+
+    assert!(client.health_check().await.is_err());
+    match recorder.take().as_slice() {
+        [Event::Start, Event::Error(message)] => {
+            assert!(message.starts_with("Failed to connect"))
+        }
+        other => panic!("expected a start and an error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn clones_share_the_observer() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let recorder = Recorder::default();
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .observer(recorder.clone())
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    assert!(client.health_check().await.unwrap());
+    assert!(clone.health_check().await.unwrap());
+    assert_eq!(recorder.take().len(), 4);
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use bb_service_rs::{BbServiceClient, BbServiceError, InputMap, ProofProgress};
+use common::{prove_body, read_request, test_circuit};
+use std::io::Write;
+use std::net::TcpListener;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn prove_with_progress(
+    response: ResponseTemplate,
+) -> (Result<Vec<u8>, BbServiceError>, Vec<ProofProgress>) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+
+    let updates = Mutex::new(Vec::new());
+    let result = BbServiceClient::new(server.uri())
+        .generate_proof_with_progress(test_circuit(), InputMap::new(), |progress| {
+            updates.lock().unwrap().push(progress)
+        })
+        .await
+        .map(|proof| proof.proof);
+    (result, updates.into_inner().unwrap())
+}
+
+fn stages(updates: &[ProofProgress]) -> Vec<&str> {
+    updates.iter().map(|update| update.stage.as_str()).collect()
+}
+
+#[tokio::test]
+async fn streamed_progress_is_reported() {
+    let body = concat!(
+        ": keep-alive\n\n",
+        "event: progress\ndata: {\"stage\": \"witness\", \"percent\": 20}\n\n",
+        "event: progress\r\ndata: {\"stage\": \"proving\"}\r\n\r\n",
+        "event: proof\ndata: {\"message\": \"Proof generated successfully\",\n",
+        "data: \"proof\": {\"proof\": [5], \"publicInputs\": []}}\n\n",
+    );
+    let (result, updates) =
+        prove_with_progress(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .await;
+
+    assert_eq!(result.unwrap(), vec![5]);
+    assert_eq!(
+        stages(&updates),
+        vec!["started", "witness", "proving", "finished"]
+    );
+    assert_eq!(updates[1].percent, Some(20.0));
+    assert_eq!(updates[2].percent, None);
+}
+
+#[tokio::test]
+async fn non_streaming_service_reports_start_and_finish() {
+    let (result, updates) =
+        prove_with_progress(ResponseTemplate::new(200).set_body_json(prove_body())).await;
+
+    assert_eq!(result.unwrap(), vec![1, 2, 3]);
+    assert_eq!(
+        stages(&updates),
+        vec![ProofProgress::STARTED, ProofProgress::FINISHED]
+    );
+}
+
+#[tokio::test]
+async fn streamed_error_is_returned() {
+    let body = concat!(
+        "event: progress\ndata: {\"stage\": \"witness\"}\n\n",
+        "event: error\ndata: {\"error\": \"Failed to generate proof\"}\n\n",
+    );
+    let (result, updates) =
+        prove_with_progress(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .await;
+
+    assert!(matches!(
+        result,
+        Err(BbServiceError::Service { status: 500, .. })
+    ));
+    assert_eq!(stages(&updates), vec!["started", "witness"]);
+}
+
+/// Serve one event stream response, writing `chunks` of its body separately
+fn chunked_event_stream(chunks: Vec<Vec<u8>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        read_request(&mut stream);
+        let _ = stream.write_all(
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n",
+        );
+        for chunk in chunks {
+            let _ = stream.write_all(&chunk);
+            let _ = stream.flush();
+            thread::sleep(Duration::from_millis(50));
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn events_split_across_chunks_are_reassembled() {
+    let body = concat!(
+        "event: progress\r\ndata: {\"stage\": \"t\u{e9}moin\"}\r\n\r\n",
+        "event: proof\r\ndata: {\"message\": \"ok\", \"proof\": {\"proof\": [5], \"publicInputs\": []}}\r\n\r\n",
+    )
+    .as_bytes();
+    // Split inside the two-byte "\u{e9}" and between the CR and LF of the
+    // first event's terminating blank line
+    let in_char = body.iter().position(|byte| *byte == 0xc3).unwrap() + 1;
+    let in_crlf = body
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .unwrap()
+        + 3;
+    let chunks = vec![
+        body[..in_char].to_vec(),
+        body[in_char..in_crlf].to_vec(),
+        body[in_crlf..].to_vec(),
+    ];
+
+    let updates = Mutex::new(Vec::new());
+    let proof = BbServiceClient::new(chunked_event_stream(chunks))
+        .generate_proof_with_progress(test_circuit(), InputMap::new(), |progress| {
+            updates.lock().unwrap().push(progress)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(proof.proof, vec![5]);
+    assert_eq!(
+        stages(&updates.into_inner().unwrap()),
+        vec!["started", "t\u{e9}moin", "finished"]
+    );
+}
@@ -0,0 +1,315 @@
+use bb_service_rs::{BbServiceError, FIELD_BYTE_SIZE, ProofData, ProofDataHex};
+use serde_json::json;
+
+fn proof_with_public_inputs(public_inputs: Vec<u8>) -> ProofData {
+    ProofData {
+        proof: vec![1, 2, 3],
+        public_inputs,
+    }
+}
+
+#[test]
+fn public_input_fields_are_hex_encoded() {
+    let mut public_inputs = vec![0u8; 64];
+    public_inputs[31] = 0x2a;
+    public_inputs[32] = 0xff;
+
+    let fields = proof_with_public_inputs(public_inputs)
+        .public_input_fields()
+        .unwrap();
+
+    assert_eq!(
+        fields,
+        vec![
+            format!("0x{}2a", "00".repeat(31)),
+            format!("0xff{}", "00".repeat(31)),
+        ]
+    );
+}
+
+#[test]
+fn public_input_fields_empty() {
+    let fields = proof_with_public_inputs(vec![])
+        .public_input_fields()
+        .unwrap();
+    assert!(fields.is_empty());
+}
+
+#[test]
+fn public_input_fields_rejects_partial_field() {
+    let result = proof_with_public_inputs(vec![0u8; 33]).public_input_fields();
+    assert!(matches!(result, Err(BbServiceError::InvalidProof(_))));
+}
+
+#[test]
+fn public_input_chunks_borrow_each_field() {
+    let mut public_inputs = vec![0u8; 64];
+    public_inputs[31] = 0x2a;
+    public_inputs[32] = 0xff;
+    let proof = proof_with_public_inputs(public_inputs);
+
+    let chunks = proof.public_input_chunks().unwrap();
+    assert_eq!(chunks.len(), 2);
+    let chunks: Vec<&[u8]> = chunks.collect();
+    assert_eq!(
+        chunks,
+        vec![&proof.public_inputs[..32], &proof.public_inputs[32..]]
+    );
+    assert_eq!(chunks[0].as_ptr(), proof.public_inputs.as_ptr());
+
+    let empty = proof_with_public_inputs(vec![]);
+    assert_eq!(empty.public_input_chunks().unwrap().count(), 0);
+}
+
+#[test]
+fn public_input_chunks_rejects_partial_field() {
+    let proof = proof_with_public_inputs(vec![0u8; FIELD_BYTE_SIZE + 1]);
+    assert!(matches!(
+        proof.public_input_chunks().map(|chunks| chunks.count()),
+        Err(BbServiceError::InvalidProof(_))
+    ));
+}
+
+#[test]
+fn hex_round_trip() {
+    let proof = ProofData {
+        proof: vec![0xde, 0xad, 0xbe, 0xef],
+        public_inputs: vec![0x00, 0x01],
+    };
+
+    let (proof_hex, public_inputs_hex) = proof.to_hex();
+    assert_eq!(proof_hex, "deadbeef");
+    assert_eq!(public_inputs_hex, "0001");
+
+    let decoded = ProofData::from_hex(&proof_hex, &public_inputs_hex).unwrap();
+    assert_eq!(decoded, proof);
+}
+
+#[test]
+fn from_hex_accepts_prefix_and_empty_inputs() {
+    let decoded = ProofData::from_hex("0xDEADBEEF", "").unwrap();
+    assert_eq!(decoded.proof, vec![0xde, 0xad, 0xbe, 0xef]);
+    assert!(decoded.public_inputs.is_empty());
+}
+
+#[test]
+fn from_hex_rejects_invalid_hex() {
+    for (proof_hex, public_inputs_hex) in [("0xzz", ""), ("abc", ""), ("00", "0x1")] {
+        assert!(matches!(
+            ProofData::from_hex(proof_hex, public_inputs_hex),
+            Err(BbServiceError::InvalidProof(_))
+        ));
+    }
+}
+
+#[test]
+fn hex_wrapper_round_trips() {
+    let proof = ProofData {
+        proof: vec![0xde, 0xad, 0xbe, 0xef],
+        public_inputs: vec![0x00, 0x01],
+    };
+
+    let hex = ProofDataHex::from_proof_data(&proof);
+    assert_eq!(hex.proof, "deadbeef");
+    assert_eq!(hex.public_inputs, "0001");
+    assert_eq!(hex.to_proof_data().unwrap(), proof);
+
+    let json = serde_json::to_value(&hex).unwrap();
+    assert_eq!(json, json!({ "proof": "deadbeef", "publicInputs": "0001" }));
+    let parsed: ProofDataHex = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, hex);
+}
+
+#[test]
+fn hex_wrapper_rejects_invalid_hex() {
+    let hex: ProofDataHex =
+        serde_json::from_value(json!({ "proof": "0xDEADBEEF", "publicInputs": "0x1" })).unwrap();
+    assert!(matches!(
+        hex.to_proof_data(),
+        Err(BbServiceError::InvalidProof(_))
+    ));
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("proof.json");
+    let path = path.to_str().unwrap();
+    let proof = ProofData {
+        proof: vec![1, 2, 3],
+        public_inputs: vec![4, 5],
+    };
+
+    proof.save_to_file(path).unwrap();
+
+    let saved: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(saved["publicInputs"], serde_json::json!([4, 5]));
+
+    let loaded = ProofData::load_from_file(path).unwrap();
+    assert_eq!(loaded, proof);
+}
+
+#[test]
+fn load_from_missing_file_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing.json");
+
+    let err = ProofData::load_from_file(path.to_str().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("Failed to read proof file"));
+    assert!(matches!(
+        err,
+        BbServiceError::Io(ref e) if e.kind() == std::io::ErrorKind::NotFound
+    ));
+
+    let path = dir.path().join("not-a-proof.json");
+    std::fs::write(&path, "{}").unwrap();
+    assert!(matches!(
+        ProofData::load_from_file(path.to_str().unwrap()),
+        Err(BbServiceError::InvalidProof(_))
+    ));
+}
+
+#[test]
+fn cloned_proof_is_equal() {
+    let proof = ProofData {
+        proof: vec![1, 2, 3],
+        public_inputs: vec![4, 5],
+    };
+
+    let mut cloned = proof.clone();
+    assert_eq!(cloned, proof);
+
+    cloned.public_inputs.push(6);
+    assert_ne!(cloned, proof);
+}
+
+#[test]
+fn calldata_round_trip() {
+    let proof = ProofData {
+        proof: vec![0xaa, 0xbb],
+        public_inputs: [[1; FIELD_BYTE_SIZE], [2; FIELD_BYTE_SIZE]].concat(),
+    };
+
+    let calldata = proof.to_calldata();
+    assert_eq!(calldata.len(), 2 * FIELD_BYTE_SIZE + 2);
+    assert_eq!(calldata[0], 1);
+    assert_eq!(&calldata[2 * FIELD_BYTE_SIZE..], &[0xaa, 0xbb]);
+
+    assert_eq!(ProofData::from_calldata(&calldata, 2).unwrap(), proof);
+}
+
+#[test]
+fn calldata_without_public_inputs() {
+    let proof = ProofData::from_calldata(&[1, 2, 3], 0).unwrap();
+    assert_eq!(proof.proof, vec![1, 2, 3]);
+    assert!(proof.public_inputs.is_empty());
+    assert_eq!(proof.to_calldata(), vec![1, 2, 3]);
+}
+
+#[test]
+fn short_calldata_is_an_error() {
+    for num_public_inputs in [2, usize::MAX] {
+        assert!(matches!(
+            ProofData::from_calldata(&[0; FIELD_BYTE_SIZE], num_public_inputs),
+            Err(BbServiceError::InvalidProof(_))
+        ));
+    }
+}
+
+#[test]
+fn sizes_are_reported() {
+    let proof = proof_with_public_inputs(vec![0; 2 * FIELD_BYTE_SIZE]);
+    assert_eq!(proof.proof_size(), 3);
+    assert_eq!(proof.num_public_inputs().unwrap(), 2);
+    assert!(!proof.is_empty());
+
+    let no_public_inputs = proof_with_public_inputs(vec![]);
+    assert_eq!(no_public_inputs.num_public_inputs().unwrap(), 0);
+
+    assert!(matches!(
+        proof_with_public_inputs(vec![0; FIELD_BYTE_SIZE + 1]).num_public_inputs(),
+        Err(BbServiceError::InvalidProof(_))
+    ));
+
+    let empty = ProofData {
+        proof: vec![],
+        public_inputs: vec![0; FIELD_BYTE_SIZE],
+    };
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn digest_covers_proof_then_public_inputs() {
+    let proof = ProofData {
+        proof: b"ab".to_vec(),
+        public_inputs: b"c".to_vec(),
+    };
+    // SHA-256 of "abc"
+    let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    assert_eq!(proof.digest(), expected);
+    assert!(proof.verify_digest(expected));
+    assert!(proof.verify_digest(&expected.to_uppercase()));
+    assert!(!proof.verify_digest(&expected[1..]));
+}
+
+#[test]
+fn byte_flip_changes_digest() {
+    let proof = proof_with_public_inputs(vec![7; FIELD_BYTE_SIZE]);
+    let digest = proof.digest();
+
+    let mut flipped = proof.clone();
+    flipped.proof[1] ^= 1;
+    assert_ne!(flipped.digest(), digest);
+    assert!(!flipped.verify_digest(&digest));
+
+    let mut flipped = proof.clone();
+    flipped.public_inputs[0] ^= 0x80;
+    assert!(!flipped.verify_digest(&digest));
+}
+
+#[test]
+fn display_summarizes_sizes() {
+    let proof = ProofData {
+        proof: vec![1; 2176],
+        public_inputs: vec![2; 2 * FIELD_BYTE_SIZE],
+    };
+    assert_eq!(
+        proof.to_string(),
+        "ProofData { proof: 2176 bytes, public_inputs: 64 bytes }"
+    );
+}
+
+#[test]
+fn debug_truncates_bytes() {
+    let proof = ProofData {
+        proof: (0..=255).collect(),
+        public_inputs: vec![0xab, 0xcd],
+    };
+    assert_eq!(
+        format!("{:?}", proof),
+        "ProofData { proof: 256 bytes [0001020304050607..], public_inputs: 2 bytes [abcd] }"
+    );
+
+    let empty = ProofData {
+        proof: vec![],
+        public_inputs: vec![],
+    };
+    assert_eq!(
+        format!("{:?}", empty),
+        "ProofData { proof: 0 bytes [], public_inputs: 0 bytes [] }"
+    );
+}
+
+#[test]
+fn serde_format_is_unchanged() {
+    let proof = ProofData {
+        proof: vec![1, 2],
+        public_inputs: vec![3],
+    };
+    assert_eq!(
+        serde_json::to_string(&proof).unwrap(),
+        r#"{"proof":[1,2],"publicInputs":[3]}"#
+    );
+}
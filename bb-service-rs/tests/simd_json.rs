@@ -0,0 +1,79 @@
+#![cfg(feature = "simd-json")]
+
+mod common;
+
+use bb_service_rs::{
+    BbServiceClient, CompiledCircuit, InputMap, OracleHash, ProofData, ProvingBackend,
+};
+use common::{fixture_path, prove_body};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// The fixture circuit with a name that needs every kind of string escaping
+fn circuit() -> CompiledCircuit {
+    let mut circuit: CompiledCircuit =
+        serde_json::from_str(&std::fs::read_to_string(fixture_path()).unwrap()).unwrap();
+    circuit["name"] = json!("quote \" backslash \\ tab \t control \u{1} unicode é ✓");
+    circuit
+}
+
+#[tokio::test]
+async fn simd_json_bodies_match_serde_json() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(prove_body()))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/verify"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Proof verification completed",
+            "isValid": true
+        })))
+        .mount(&server)
+        .await;
+
+    let client = BbServiceClient::builder()
+        .base_url(server.uri())
+        .backend(ProvingBackend::UltraHonk)
+        .oracle_hash(OracleHash::Keccak)
+        .recursive(true)
+        .build()
+        .unwrap();
+    let circuit = circuit();
+    let input = InputMap::from([("x".to_string(), json!(["1", "0x2a"]))]);
+    let proof = ProofData {
+        proof: vec![1, 2, 3],
+        public_inputs: vec![4, 5, 6],
+    };
+    client
+        .generate_proof(&circuit, input.clone())
+        .await
+        .unwrap();
+    client.verify_proof(&circuit, proof.clone()).await.unwrap();
+
+    let expected = [
+        json!({
+            "circuit": circuit,
+            "input": input,
+            "backend": "ultra_honk",
+            "oracleHash": "keccak",
+            "recursive": true
+        }),
+        json!({
+            "circuit": circuit,
+            "proof": proof,
+            "backend": "ultra_honk",
+            "oracleHash": "keccak"
+        }),
+    ];
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    for (request, expected) in requests.iter().zip(expected) {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body, expected);
+        assert_eq!(request.body.len(), serde_json::to_vec(&body).unwrap().len());
+    }
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use bb_service_rs::{BbServiceClient, BbServiceError, InputMap, ProofMeta};
+use common::test_circuit;
+use serde_json::json;
+use std::io::{self, Write};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn server_responding(response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn prove_to_vec(response: ResponseTemplate) -> Result<(ProofMeta, Vec<u8>), BbServiceError> {
+    let server = server_responding(response).await;
+    let mut written = Vec::new();
+    let meta = BbServiceClient::new(server.uri())
+        .generate_proof_to_writer(test_circuit(), InputMap::new(), &mut written)
+        .await?;
+    Ok((meta, written))
+}
+
+#[tokio::test]
+async fn proof_is_written_to_writer() {
+    let proof: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let (meta, written) = prove_to_vec(ResponseTemplate::new(200).set_body_json(json!({
+        "message": "Proof \"generated\" {successfully} [ok]",
+        "proof": { "publicInputs": [7, 8], "proof": proof, "extra": [[1], { "proof": [9] }] }
+    })))
+    .await
+    .unwrap();
+
+    assert_eq!(written, proof);
+    assert_eq!(
+        meta,
+        ProofMeta {
+            proof_len: 4096,
+            public_inputs: vec![7, 8],
+        }
+    );
+    assert_eq!(meta.public_inputs_len(), 2);
+}
+
+#[tokio::test]
+async fn malformed_responses_are_invalid() {
+    let bodies = [
+        r#"{"message": "ok"}"#,
+        r#"{"message": "ok", "proof": {"proof": [1, 2"#,
+        r#"{"message": "ok", "proof": {"proof": [256], "publicInputs": []}}"#,
+        r#"{"message": "ok", "proof": {"proof": [1], "publicInputs": []]}"#,
+        r#"{"message": "ok", "proof": {"proof": [1], "publicInputs": []}} {}"#,
+    ];
+    for body in bodies {
+        let result =
+            prove_to_vec(ResponseTemplate::new(200).set_body_raw(body, "application/json")).await;
+        assert!(
+            matches!(result, Err(BbServiceError::InvalidResponse)),
+            "{}",
+            body
+        );
+    }
+}
+
+#[tokio::test]
+async fn non_json_content_type_is_rejected_before_writing() {
+    let body = r#"{"message": "ok", "proof": {"proof": [1, 2], "publicInputs": []}}"#;
+    let server = server_responding(
+        ResponseTemplate::new(200).set_body_raw(body, "text/html; charset=utf-8"),
+    )
+    .await;
+
+    let mut written = Vec::new();
+    let result = BbServiceClient::new(server.uri())
+        .generate_proof_to_writer(test_circuit(), InputMap::new(), &mut written)
+        .await;
+
+    match result {
+        Err(BbServiceError::MalformedResponse {
+            status,
+            length,
+            reason,
+        }) => {
+            assert_eq!(status, 200);
+            assert_eq!(length, body.len());
+            assert_eq!(reason, "expected a JSON body, got text/html; charset=utf-8");
+        }
+        other => panic!("expected a malformed response, got {:?}", other),
+    }
+    assert!(written.is_empty());
+}
+
+#[tokio::test]
+async fn writer_errors_are_returned() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let server = server_responding(ResponseTemplate::new(200).set_body_json(json!({
+        "message": "Proof generated successfully",
+        "proof": { "proof": [1, 2, 3], "publicInputs": [] }
+    })))
+    .await;
+
+    let result = BbServiceClient::new(server.uri())
+        .generate_proof_to_writer(test_circuit(), InputMap::new(), FailingWriter)
+        .await;
+    assert!(matches!(result, Err(BbServiceError::Io(_))));
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use bb_service_rs::{BbServiceClient, InputMap};
+use common::{prove_body, test_circuit};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Collects formatted trace output in memory
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Output {
+    type Writer = Output;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl Output {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn spans_record_url_status_and_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/prove"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(prove_body()))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/verify"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let output = Output::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(output.clone())
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let client = BbServiceClient::new(server.uri());
+    let proof = client
+        .generate_proof(test_circuit(), InputMap::new())
+        .await
+        .unwrap();
+    assert!(client.verify_proof(test_circuit(), proof).await.is_err());
+
+    let contents = output.contents();
+    assert!(contents.contains(&format!("url={}/prove", server.uri())));
+    assert!(contents.contains("status=200"));
+    assert!(contents.contains("status=500"));
+    assert!(contents.contains("elapsed_ms="));
+    assert!(contents.contains("ERROR"));
+}
@@ -0,0 +1,35 @@
+//! Checks that the async client builds for the browser. The other test
+//! binaries rely on a native mock server, so run only this one:
+//! `wasm-pack test --node -- --test wasm`
+
+#![cfg(target_arch = "wasm32")]
+
+use bb_service_rs::{BbServiceClient, InputBuilder, ProofData, parse_circuit_definition};
+use std::time::Duration;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn client_builds() {
+    let client = BbServiceClient::builder()
+        .base_url("https://bb.example.com".to_string())
+        .timeout(Duration::from_secs(60))
+        .max_retries(2)
+        .build()
+        .unwrap();
+
+    let circuit = parse_circuit_definition(br#"{"bytecode": "H4sI", "abi": {}}"#).unwrap();
+    let input = InputBuilder::new().field("x", 1).build();
+
+    // Building the futures checks the client's async API is available without
+    // sending anything
+    let client = client.clone();
+    let _prove = client.generate_proof(circuit.clone(), input);
+    let _verify = client.verify_proof(
+        circuit,
+        ProofData {
+            proof: vec![1],
+            public_inputs: vec![],
+        },
+    );
+    let _health = client.health_check();
+}